@@ -0,0 +1,97 @@
+//! Criterion benchmarks for every part of every scaffolded day, run against the real inputs.
+//! The list of days is generated by `build.rs`, see the readme for usage.
+use std::path::Path;
+
+use advent_of_code::template::{read_file, Day, ParamSet, Solution};
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+
+include!(concat!(env!("OUT_DIR"), "/days.rs"));
+
+fn bench_day<A, B>(
+    c: &mut Criterion,
+    day: Day,
+    part_one: impl Fn(&str) -> Option<A>,
+    part_two: impl Fn(&str) -> Option<B>,
+) {
+    // skip days whose input has not been downloaded yet.
+    if !Path::new(&format!("data/inputs/{day}.txt")).exists() {
+        return;
+    }
+
+    let input = read_file("inputs", day);
+    let mut group = c.benchmark_group(format!("Day {day}"));
+
+    group.bench_function("Part 1", |b| b.iter(|| part_one(black_box(&input))));
+    group.bench_function("Part 2", |b| b.iter(|| part_two(black_box(&input))));
+
+    group.finish();
+}
+
+// only generated for days registering a single part.
+#[allow(dead_code)]
+fn bench_part<A>(c: &mut Criterion, day: Day, part: u8, solve: impl Fn(&str) -> Option<A>) {
+    // skip days whose input has not been downloaded yet.
+    if !Path::new(&format!("data/inputs/{day}.txt")).exists() {
+        return;
+    }
+
+    let input = read_file("inputs", day);
+    let mut group = c.benchmark_group(format!("Day {day}"));
+
+    group.bench_function(format!("Part {part}"), |b| {
+        b.iter(|| solve(black_box(&input)))
+    });
+
+    group.finish();
+}
+
+fn bench_parsed_day<P, A, B>(
+    c: &mut Criterion,
+    day: Day,
+    parse: fn(&str) -> P,
+    part_one: impl Fn(&P) -> Option<A>,
+    part_two: impl Fn(&P) -> Option<B>,
+) {
+    // skip days whose input has not been downloaded yet.
+    if !Path::new(&format!("data/inputs/{day}.txt")).exists() {
+        return;
+    }
+
+    let input = read_file("inputs", day);
+    let parsed = parse(&input);
+    let mut group = c.benchmark_group(format!("Day {day}"));
+
+    group.bench_function("Parse", |b| b.iter(|| parse(black_box(&input))));
+    group.bench_function("Part 1", |b| b.iter(|| part_one(black_box(&parsed))));
+    group.bench_function("Part 2", |b| b.iter(|| part_two(black_box(&parsed))));
+
+    group.finish();
+}
+
+fn bench_solution<S: Solution>(c: &mut Criterion, day: Day) {
+    // skip days whose input has not been downloaded yet.
+    if !Path::new(&format!("data/inputs/{day}.txt")).exists() {
+        return;
+    }
+
+    let input = read_file("inputs", day);
+    let params = S::Params::real();
+    let parsed = S::parse(&input);
+    let mut carry = S::Carry::default();
+    S::part_one(&parsed, &params, &mut carry);
+
+    let mut group = c.benchmark_group(format!("Day {day}"));
+
+    group.bench_function("Parse", |b| b.iter(|| S::parse(black_box(&input))));
+    group.bench_function("Part 1", |b| {
+        b.iter(|| S::part_one(black_box(&parsed), &params, &mut S::Carry::default()))
+    });
+    group.bench_function("Part 2", |b| {
+        b.iter(|| S::part_two(black_box(&parsed), &params, black_box(&carry)))
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_days);
+criterion_main!(benches);
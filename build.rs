@@ -0,0 +1,153 @@
+//! Generates the list of benchmarks for `benches/days.rs` when the `bench` feature is enabled.
+//! Every scaffolded solution in `src/bin` is included as a module so its parts can be benched directly.
+use std::{env, fs, path::Path};
+
+/// How a day registers its solution with the `solution!` macro.
+enum Registration {
+    /// Free `part_one` and `part_two` functions taking the input.
+    Functions,
+    /// A single free function for the given part, e.g. `solution!(25, 1)`.
+    Part(u8),
+    /// Free functions taking the output of the named parse function.
+    Parsed(String),
+    /// A type implementing the `Solution` trait.
+    Solution(String),
+}
+
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+
+    if env::var_os("CARGO_FEATURE_BENCH").is_none() {
+        return;
+    }
+
+    // only watch the solutions when generating benchmarks, so editing a day does not rebuild the library.
+    println!("cargo:rerun-if-changed=src/bin");
+
+    let manifest_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
+    let bin_dir = Path::new(&manifest_dir).join("src").join("bin");
+
+    let mut days: Vec<(u8, Registration, Option<String>)> = fs::read_dir(&bin_dir)
+        .unwrap()
+        .filter_map(|entry| {
+            let name = entry.ok()?.file_name().into_string().ok()?;
+            let day = name.strip_suffix(".rs")?;
+            if day.len() == 2 {
+                let source = fs::read_to_string(bin_dir.join(&name)).ok()?;
+                let args = find_invocation(&source).map_or(vec![], |args| {
+                    args.split(',').map(str::trim).collect::<Vec<_>>()
+                });
+                Some((
+                    day.parse().ok()?,
+                    find_registration(&args),
+                    find_named(&args, "params"),
+                ))
+            } else {
+                None
+            }
+        })
+        .collect();
+    days.sort_unstable_by_key(|(day, _, _)| *day);
+
+    let mut lines: Vec<String> = vec![];
+
+    for (day, _, _) in &days {
+        let path = bin_dir.join(format!("{day:02}.rs"));
+        lines.push(format!("#[path = {:?}]", path.display().to_string()));
+        lines.push("#[allow(dead_code, unused_imports)]".into());
+        lines.push(format!("mod day_{day:02};"));
+    }
+
+    lines.push(String::new());
+    lines.push("fn bench_days(c: &mut criterion::Criterion) {".into());
+    for (day, registration, params) in &days {
+        let module = format!("day_{day:02}");
+        // parts of days with parameters are benched with the real values, bound in a closure.
+        let part = |name: &str| match params {
+            Some(_) => format!("|input| {module}::{name}(input, &params)"),
+            None => format!("{module}::{name}"),
+        };
+
+        if let Some(params) = params {
+            lines.push(format!(
+                "    let params = <{module}::{params} as advent_of_code::template::ParamSet>::real();"
+            ));
+        }
+        lines.push(match registration {
+            Registration::Functions => format!(
+                "    bench_day(c, advent_of_code::day!({day}), {}, {});",
+                part("part_one"),
+                part("part_two")
+            ),
+            Registration::Part(1) => format!(
+                "    bench_part(c, advent_of_code::day!({day}), 1, {});",
+                part("part_one")
+            ),
+            Registration::Part(_) => format!(
+                "    bench_part(c, advent_of_code::day!({day}), 2, {});",
+                part("part_two")
+            ),
+            Registration::Parsed(parse) => format!(
+                "    bench_parsed_day(c, advent_of_code::day!({day}), {module}::{parse}, {}, {});",
+                part("part_one"),
+                part("part_two")
+            ),
+            Registration::Solution(solution) => format!(
+                "    bench_solution::<{module}::{solution}>(c, advent_of_code::day!({day}));"
+            ),
+        });
+    }
+    lines.push("}".into());
+
+    let out_dir = env::var("OUT_DIR").unwrap();
+    fs::write(Path::new(&out_dir).join("days.rs"), lines.join("\n")).unwrap();
+}
+
+fn take_ident(s: &str) -> String {
+    s.trim_start()
+        .chars()
+        .take_while(|c| c.is_alphanumeric() || *c == '_')
+        .collect()
+}
+
+/// Returns the arguments of the item-level `solution!` invocation, i.e. one starting a line, so
+/// mentions in comments or strings are skipped.
+fn find_invocation(source: &str) -> Option<&str> {
+    let mut offset = 0;
+
+    for line in source.split_inclusive('\n') {
+        let rest = line
+            .strip_prefix("advent_of_code::solution!(")
+            .or_else(|| line.strip_prefix("solution!("));
+        if let Some(rest) = rest {
+            let start = offset + line.len() - rest.len();
+            return source[start..].split_once(')').map(|(args, _)| args);
+        }
+        offset += line.len();
+    }
+
+    None
+}
+
+/// Returns the value of a named argument of the `solution!` invocation, e.g. `parse = parse_input`.
+fn find_named(args: &[&str], name: &str) -> Option<String> {
+    args.iter()
+        .find_map(|arg| arg.strip_prefix(name)?.trim_start().strip_prefix('='))
+        .map(take_ident)
+}
+
+/// Inspects the arguments of the `solution!` invocation of a day.
+fn find_registration(args: &[&str]) -> Registration {
+    if let Some(parse) = find_named(args, "parse") {
+        return Registration::Parsed(parse);
+    }
+
+    match args.get(1) {
+        Some(&"1") => Registration::Part(1),
+        Some(&"2") => Registration::Part(2),
+        Some(arg) if arg.starts_with(|c: char| c.is_alphabetic()) && !arg.contains('=') => {
+            Registration::Solution(take_ident(arg))
+        }
+        _ => Registration::Functions,
+    }
+}
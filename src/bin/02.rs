@@ -1,3 +1,5 @@
+use std::ops::RangeInclusive;
+
 use nom::{
     bytes::complete::tag,
     character::{self, complete::newline},
@@ -6,7 +8,7 @@ use nom::{
     IResult,
 };
 
-advent_of_code::solution!(2);
+advent_of_code::solution!(2, params = Params);
 
 fn parse_report(input: &str) -> IResult<&str, Vec<i32>> {
     separated_list0(tag(" "), character::complete::i32)(input)
@@ -19,64 +21,81 @@ fn parse_reports(input: &str) -> Vec<Vec<i32>> {
     reports
 }
 
-fn is_safe(report: &[i32]) -> bool {
-    assert!(report.len() >= 2);
-
-    let mut safe_inc = true;
-    let mut safe_dec = true;
-
-    report
-        .windows(2)
-        .map(|window| window[1] - window[0])
-        .for_each(|diff| {
-            if !(1..=3).contains(&diff) {
-                safe_inc = false;
-            }
-            if !(-3..=-1).contains(&diff) {
-                safe_dec = false;
-            }
-        });
-
-    safe_inc || safe_dec
+advent_of_code::params! {
+    /// Number of bad levels the problem dampener tolerates.
+    tolerance: usize = 1;
 }
 
-fn is_dampener_safe(report: &[i32]) -> bool {
-    assert!(report.len() >= 2);
+const INCREASING: RangeInclusive<i32> = 1..=3;
+const DECREASING: RangeInclusive<i32> = -3..=-1;
+
+/// Fewest levels to remove so that all differences between the remaining levels are in `steps`,
+/// `None` if that takes more than `tolerance` removals.
+fn removals_for_steps(
+    report: &[i32],
+    tolerance: usize,
+    steps: &RangeInclusive<i32>,
+) -> Option<Vec<usize>> {
+    if report.is_empty() {
+        return Some(vec![]);
+    }
 
-    for i in 0..report.len() {
-        let mut copy: Vec<i32> = report.to_vec();
-        copy.remove(i);
+    // fewest removals with level `i` kept last, and the level kept before it. Only the last
+    // `tolerance + 1` levels can precede a kept level, so this is a single pass over the report.
+    let mut best: Vec<Option<(usize, Option<usize>)>> = Vec::with_capacity(report.len());
+
+    for (i, level) in report.iter().enumerate() {
+        // on ties, prefer keeping the closest level, i.e. removing earlier levels.
+        let continued = (i.saturating_sub(tolerance + 1)..i)
+            .rev()
+            .filter(|j| steps.contains(&(level - report[*j])))
+            .filter_map(|j| best[j].map(|(removed, _)| (removed + i - j - 1, Some(j))));
+        let start = (i <= tolerance).then_some((i, None));
+
+        best.push(
+            continued
+                .chain(start)
+                .filter(|(removed, _)| *removed <= tolerance)
+                .min_by_key(|(removed, _)| *removed),
+        );
+    }
 
-        if is_safe(&copy) {
-            return true;
-        }
+    let last = report.len() - 1;
+    let (mut kept, _) = (last.saturating_sub(tolerance)..=last)
+        .filter_map(|i| best[i].map(|(removed, _)| (Some(i), removed + last - i)))
+        .filter(|(_, removed)| *removed <= tolerance)
+        .min_by_key(|(_, removed)| *removed)?;
+
+    let mut is_kept = vec![false; report.len()];
+    while let Some(i) = kept {
+        is_kept[i] = true;
+        kept = best[i].and_then(|(_, previous)| previous);
     }
 
-    false
+    Some((0..report.len()).filter(|i| !is_kept[*i]).collect())
 }
 
-pub fn part_one(input: &str) -> Option<usize> {
-    let reports = parse_reports(input);
+/// Indices of the fewest levels to remove to make the report safe, `None` if it takes more than `tolerance`.
+fn removals_to_safe(report: &[i32], tolerance: usize) -> Option<Vec<usize>> {
+    [INCREASING, DECREASING]
+        .iter()
+        .filter_map(|steps| removals_for_steps(report, tolerance, steps))
+        .min_by_key(Vec::len)
+}
 
-    Some(
-        reports
-            .iter()
-            .map(|report| is_safe(report))
-            .filter(|b| *b)
-            .count(),
-    )
+fn count_safe(input: &str, tolerance: usize) -> usize {
+    parse_reports(input)
+        .iter()
+        .filter(|report| removals_to_safe(report, tolerance).is_some())
+        .count()
 }
 
-pub fn part_two(input: &str) -> Option<usize> {
-    let reports = parse_reports(input);
+pub fn part_one(input: &str, _params: &Params) -> Option<usize> {
+    Some(count_safe(input, 0))
+}
 
-    Some(
-        reports
-            .iter()
-            .map(|report| is_dampener_safe(report))
-            .filter(|b| *b)
-            .count(),
-    )
+pub fn part_two(input: &str, params: &Params) -> Option<usize> {
+    Some(count_safe(input, params.tolerance))
 }
 
 #[cfg(test)]
@@ -85,13 +104,87 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let (input, params) = advent_of_code::template::read_file_with_params("examples", DAY);
+        let result = part_one(&input, &params);
         assert_eq!(result, Some(2));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let (input, params) = advent_of_code::template::read_file_with_params("examples", DAY);
+        let result = part_two(&input, &params);
         assert_eq!(result, Some(4));
     }
+
+    #[test]
+    fn test_reports_removed_level() {
+        assert_eq!(removals_to_safe(&[7, 6, 4, 2, 1], 1), Some(vec![]));
+        assert_eq!(removals_to_safe(&[1, 3, 2, 4, 5], 1), Some(vec![1]));
+        assert_eq!(removals_to_safe(&[8, 6, 4, 4, 1], 1), Some(vec![2]));
+        assert_eq!(removals_to_safe(&[1, 2, 7, 8, 9], 1), None);
+        assert_eq!(removals_to_safe(&[1, 2, 7, 8, 9], 2), Some(vec![0, 1]));
+        // the first and last level can be dropped too.
+        assert_eq!(removals_to_safe(&[9, 1, 2, 3], 1), Some(vec![0]));
+        assert_eq!(removals_to_safe(&[1, 2, 3, 9], 1), Some(vec![3]));
+    }
+
+    /// The original check, which is what the dampener has to match.
+    fn is_safe(report: &[i32]) -> bool {
+        let diffs: Vec<i32> = report.windows(2).map(|w| w[1] - w[0]).collect();
+
+        diffs.iter().all(|diff| INCREASING.contains(diff))
+            || diffs.iter().all(|diff| DECREASING.contains(diff))
+    }
+
+    /// Fewest removals by trying every set of removed levels.
+    fn brute_force(report: &[i32], tolerance: usize) -> Option<usize> {
+        (0u32..1 << report.len())
+            .filter(|removed| removed.count_ones() as usize <= tolerance)
+            .filter(|removed| {
+                let kept: Vec<i32> = (0..report.len())
+                    .filter(|i| removed & (1 << i) == 0)
+                    .map(|i| report[i])
+                    .collect();
+                is_safe(&kept)
+            })
+            .map(|removed| removed.count_ones() as usize)
+            .min()
+    }
+
+    #[test]
+    fn test_against_brute_force() {
+        let mut rng = fastrand::Rng::with_seed(0x2024_1202);
+
+        for _ in 0..5_000 {
+            let len = rng.usize(0..10);
+            let tolerance = rng.usize(0..4);
+            // mostly small steps in one direction, so that some reports are safe.
+            let direction = if rng.bool() { 1 } else { -1 };
+            let mut level = 50;
+            let report: Vec<i32> = (0..len)
+                .map(|_| {
+                    level += match rng.u64(0..5) {
+                        0 => rng.i32(-5..=5),
+                        _ => direction * rng.i32(1..=3),
+                    };
+                    level
+                })
+                .collect();
+
+            let removed = removals_to_safe(&report, tolerance);
+            assert_eq!(
+                removed.as_ref().map(Vec::len),
+                brute_force(&report, tolerance),
+                "{report:?} {tolerance}"
+            );
+
+            if let Some(removed) = removed {
+                let kept: Vec<i32> = (0..len)
+                    .filter(|i| !removed.contains(i))
+                    .map(|i| report[i])
+                    .collect();
+                assert!(is_safe(&kept), "{report:?} {removed:?}");
+            }
+        }
+    }
 }
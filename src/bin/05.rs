@@ -1,4 +1,5 @@
-use std::cmp::Ordering;
+use std::collections::{HashMap, VecDeque};
+use std::fmt::Display;
 
 use nom::{
     bytes::complete::tag,
@@ -8,11 +9,80 @@ use nom::{
     IResult,
 };
 
-advent_of_code::solution!(5);
+advent_of_code::solution!(5, parse = parse_input);
 
 type OrderingRule = (u32, u32);
 type UpdatePages = Vec<u32>;
 
+pub struct SafetyManual {
+    order: PageOrder,
+    updates: Vec<UpdatePages>,
+}
+
+/// The ordering rules as a bitset relation: `before[i]` has bit `j` set if page `i` must come before page `j`.
+struct PageOrder {
+    indices: HashMap<u32, usize>,
+    before: Vec<Vec<u64>>,
+}
+
+impl PageOrder {
+    fn new(rules: &[OrderingRule]) -> Self {
+        let mut indices = HashMap::new();
+        for page in rules.iter().flat_map(|(before, after)| [*before, *after]) {
+            let next = indices.len();
+            indices.entry(page).or_insert(next);
+        }
+
+        let words = indices.len().div_ceil(64);
+        let mut before = vec![vec![0; words]; indices.len()];
+        for (lhs, rhs) in rules {
+            let j = indices[rhs];
+            before[indices[lhs]][j / 64] |= 1 << (j % 64);
+        }
+
+        Self { indices, before }
+    }
+
+    /// Bit indices of the pages of an update, `None` for pages without rules. Look them up once per update.
+    fn indices(&self, pages: &[u32]) -> Vec<Option<usize>> {
+        pages
+            .iter()
+            .map(|page| self.indices.get(page).copied())
+            .collect()
+    }
+
+    /// Whether a rule requires the page with bit index `lhs` to be printed before the one with `rhs`.
+    fn requires(&self, lhs: Option<usize>, rhs: Option<usize>) -> bool {
+        match (lhs, rhs) {
+            (Some(i), Some(j)) => self.before[i][j / 64] & (1 << (j % 64)) != 0,
+            _ => false,
+        }
+    }
+}
+
+/// Why the pages of an update cannot be put into a single order.
+#[derive(Debug, PartialEq, Eq)]
+enum OrderingError {
+    /// The rules contain a cycle through these pages.
+    Cycle(Vec<u32>),
+    /// No rule decides the order of these pages.
+    Ambiguous(u32, u32),
+}
+
+impl Display for OrderingError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            OrderingError::Cycle(pages) => {
+                let pages: Vec<String> = pages.iter().map(u32::to_string).collect();
+                write!(f, "the rules contain the cycle {}", pages.join(" < "))
+            }
+            OrderingError::Ambiguous(lhs, rhs) => {
+                write!(f, "no rule orders pages {lhs} and {rhs}")
+            }
+        }
+    }
+}
+
 fn parse_ordering_rule(input: &str) -> IResult<&str, OrderingRule> {
     separated_pair(character::complete::u32, tag("|"), character::complete::u32)(input)
 }
@@ -32,36 +102,95 @@ fn parse_update(input: &str) -> IResult<&str, UpdatePages> {
 fn parse_updates(input: &str) -> IResult<&str, Vec<UpdatePages>> {
     many0(parse_update)(input)
 }
-fn parse_input(input: &str) -> (Vec<OrderingRule>, Vec<UpdatePages>) {
+
+pub fn parse_input(input: &str) -> SafetyManual {
     let (input, rules) =
         separated_list0(newline, parse_ordering_rule)(input).expect("rules should parse");
     let (input, _) = strip_newlines(input).expect("shouldn't fail");
     let (_, updates) = parse_updates(input).expect("updates should parse");
 
-    (rules, updates)
+    SafetyManual {
+        order: PageOrder::new(&rules),
+        updates,
+    }
 }
 
-fn cmp_pages(lhs: u32, rhs: u32, rules: &[OrderingRule]) -> Ordering {
-    for (before, after) in rules {
-        if *before == lhs && *after == rhs {
-            return Ordering::Less;
-        }
-        if *before == rhs && *after == lhs {
-            return Ordering::Greater;
-        }
-    }
+/// An update is in order if no rule requires a later page to come before an earlier one.
+fn correctly_ordered(pages: &[u32], order: &PageOrder) -> bool {
+    let indices = order.indices(pages);
 
-    Ordering::Equal
+    indices.iter().enumerate().all(|(i, lhs)| {
+        indices[i + 1..]
+            .iter()
+            .all(|rhs| !order.requires(*rhs, *lhs))
+    })
 }
 
-fn correctly_ordered(pages: &[u32], rules: &[OrderingRule]) -> bool {
-    pages.is_sorted_by(|lhs, rhs| cmp_pages(*lhs, *rhs, rules) != Ordering::Greater)
+/// Finds a cycle among the `remaining` positions of an update, given that each has a predecessor within them.
+fn find_cycle(
+    pages: &[u32],
+    remaining: &[usize],
+    requires: impl Fn(usize, usize) -> bool,
+) -> Vec<u32> {
+    let mut path = vec![remaining[0]];
+
+    loop {
+        let current = *path.last().expect("path is never empty");
+        let predecessor = *remaining
+            .iter()
+            .find(|position| requires(**position, current))
+            .expect("every remaining page has a predecessor");
+
+        if let Some(start) = path.iter().position(|position| *position == predecessor) {
+            let mut cycle: Vec<u32> = path[start..].iter().rev().map(|i| pages[*i]).collect();
+            // start at the smallest page, so the same cycle is always reported the same way.
+            let smallest = (0..cycle.len()).min_by_key(|i| cycle[*i]).unwrap_or(0);
+            cycle.rotate_left(smallest);
+            return cycle;
+        }
+        path.push(predecessor);
+    }
 }
 
-fn correct_ordering(mut pages: Vec<u32>, rules: &[OrderingRule]) -> Vec<u32> {
-    pages.sort_by(|lhs, rhs| cmp_pages(*lhs, *rhs, rules));
+/// Sorts the pages topologically with Kahn's algorithm. Fails if the rules are cyclic or do not
+/// determine a unique order, i.e. if more than one page is ready to be printed next.
+fn correct_ordering(pages: &[u32], order: &PageOrder) -> Result<Vec<u32>, OrderingError> {
+    let indices = order.indices(pages);
+    let requires = |lhs: usize, rhs: usize| order.requires(indices[lhs], indices[rhs]);
+
+    // positions of the pages that have to follow each page, and the number of pages each has to follow.
+    let successors: Vec<Vec<usize>> = (0..pages.len())
+        .map(|lhs| (0..pages.len()).filter(|rhs| requires(lhs, *rhs)).collect())
+        .collect();
+    let mut in_degree = vec![0; pages.len()];
+    for rhs in successors.iter().flatten() {
+        in_degree[*rhs] += 1;
+    }
+
+    let mut ready: VecDeque<usize> = (0..pages.len()).filter(|i| in_degree[*i] == 0).collect();
+    let mut sorted = Vec::with_capacity(pages.len());
+
+    while sorted.len() < pages.len() {
+        let Some(next) = ready.pop_front() else {
+            // every page left has to follow another one left.
+            let remaining: Vec<usize> = (0..pages.len()).filter(|i| in_degree[*i] > 0).collect();
+            let cycle = find_cycle(pages, &remaining, requires);
+            return Err(OrderingError::Cycle(cycle));
+        };
+        if let Some(other) = ready.front() {
+            return Err(OrderingError::Ambiguous(pages[next], pages[*other]));
+        }
+
+        sorted.push(pages[next]);
+        for rhs in &successors[next] {
+            in_degree[*rhs] -= 1;
+            if in_degree[*rhs] == 0 {
+                ready.push_back(*rhs);
+            }
+        }
+    }
 
-    pages
+    Ok(sorted)
 }
 
 fn middle_page(pages: &[u32]) -> Option<u32> {
@@ -72,28 +201,31 @@ fn middle_page(pages: &[u32]) -> Option<u32> {
     None
 }
 
-pub fn part_one(input: &str) -> Option<u32> {
-    let (rules, updates) = parse_input(input);
-
+pub fn part_one(manual: &SafetyManual) -> Option<u32> {
     Some(
-        updates
+        manual
+            .updates
             .iter()
-            .filter(|update| correctly_ordered(update, &rules))
+            .filter(|update| correctly_ordered(update, &manual.order))
             .map(|update| middle_page(update).unwrap())
             .sum(),
     )
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
-    let (rules, updates) = parse_input(input);
+pub fn part_two(manual: &SafetyManual) -> Option<u32> {
     let mut sum = 0;
 
-    for update in updates {
-        if correctly_ordered(&update, &rules) {
+    for update in &manual.updates {
+        if correctly_ordered(update, &manual.order) {
             continue;
         }
-        let corrected = correct_ordering(update.clone(), &rules);
-        sum += middle_page(&corrected).expect("should be a middle page");
+        match correct_ordering(update, &manual.order) {
+            Ok(corrected) => sum += middle_page(&corrected).expect("should be a middle page"),
+            Err(e) => {
+                eprintln!("Cannot correct update {update:?}: {e}.");
+                return None;
+            }
+        }
     }
 
     Some(sum)
@@ -105,13 +237,51 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let manual = parse_input(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&manual);
         assert_eq!(result, Some(143));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let manual = parse_input(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&manual);
         assert_eq!(result, Some(123));
     }
+
+    #[test]
+    fn test_correct_ordering() {
+        let manual = parse_input(&advent_of_code::template::read_file("examples", DAY));
+        let result = correct_ordering(&[97, 13, 75, 29, 47], &manual.order);
+        assert_eq!(result, Ok(vec![97, 75, 47, 29, 13]));
+    }
+
+    #[test]
+    fn test_cyclic_rules() {
+        let order = PageOrder::new(&[(1, 2), (2, 3), (3, 1), (4, 1)]);
+        assert_eq!(
+            correct_ordering(&[4, 3, 2, 1], &order),
+            Err(OrderingError::Cycle(vec![1, 2, 3]))
+        );
+        assert_eq!(
+            OrderingError::Cycle(vec![1, 2, 3]).to_string(),
+            "the rules contain the cycle 1 < 2 < 3"
+        );
+    }
+
+    #[test]
+    fn test_insufficient_rules() {
+        let order = PageOrder::new(&[(1, 2), (1, 3)]);
+        assert!(correctly_ordered(&[1, 3, 2], &order));
+        assert_eq!(
+            correct_ordering(&[3, 2, 1], &order),
+            Err(OrderingError::Ambiguous(3, 2))
+        );
+        // pages without rules are never out of order, but cannot be sorted either.
+        assert!(correctly_ordered(&[7, 5], &order));
+        assert_eq!(
+            correct_ordering(&[7, 5], &order),
+            Err(OrderingError::Ambiguous(7, 5))
+        );
+    }
 }
@@ -1,5 +1,3 @@
-use std::collections::HashSet;
-
 use grid_2d::{Coord, Grid, Size};
 use rayon::prelude::*;
 
@@ -66,51 +64,173 @@ fn rotate_90(direction: Direction) -> Direction {
     }
 }
 
+fn offset(direction: Direction) -> Coord {
+    match direction {
+        Direction::North => Coord::new(0, -1),
+        Direction::West => Coord::new(-1, 0),
+        Direction::East => Coord::new(1, 0),
+        Direction::South => Coord::new(0, 1),
+    }
+}
+
+const DIRECTIONS: [Direction; 4] = [
+    Direction::North,
+    Direction::West,
+    Direction::East,
+    Direction::South,
+];
+
+fn direction_index(direction: Direction) -> usize {
+    match direction {
+        Direction::North => 0,
+        Direction::West => 1,
+        Direction::East => 2,
+        Direction::South => 3,
+    }
+}
+
+fn cell_index(size: Size, position: Coord) -> usize {
+    position.y as usize * size.width() as usize + position.x as usize
+}
+
+/// A fixed size set of indices.
+struct BitSet(Vec<u64>);
+
+impl BitSet {
+    fn new(len: usize) -> Self {
+        Self(vec![0; len.div_ceil(64)])
+    }
+
+    /// Adds `index`, returns `false` if it was already present.
+    fn insert(&mut self, index: usize) -> bool {
+        let (word, bit) = (index / 64, 1 << (index % 64));
+        let is_new = self.0[word] & bit == 0;
+        self.0[word] |= bit;
+        is_new
+    }
+}
+
 fn next_position(
     grid: &Grid<Cell>,
     position: Coord,
     direction: Direction,
 ) -> Option<(Coord, Direction)> {
-    let next = match direction {
-        Direction::North => position + Coord::new(0, -1),
-        Direction::West => position + Coord::new(-1, 0),
-        Direction::East => position + Coord::new(1, 0),
-        Direction::South => position + Coord::new(0, 1),
-    };
+    let mut direction = direction;
+
+    // a guard boxed in by four obstacles keeps turning on the spot.
+    for _ in 0..4 {
+        let next = position + offset(direction);
 
-    match grid.get(next) {
-        Some(Cell::Empty) => Some((next, direction)),
-        Some(Cell::Blocked) => next_position(grid, position, rotate_90(direction)),
-        None => None,
+        match grid.get(next) {
+            Some(Cell::Empty) => return Some((next, direction)),
+            Some(Cell::Blocked) => direction = rotate_90(direction),
+            None => return None,
+        }
     }
+
+    Some((position, direction))
 }
 
-fn find_path(grid: &Grid<Cell>, mut position: Coord, mut direction: Direction) -> HashSet<Coord> {
-    let mut seen_positions = HashSet::<Coord>::new();
-    seen_positions.insert(position);
+/// Every state of the guard, step by step, until it leaves the map. Panics if the guard loops.
+fn walk(
+    grid: &Grid<Cell>,
+    mut position: Coord,
+    mut direction: Direction,
+) -> Vec<(Coord, Direction)> {
+    let mut states = vec![(position, direction)];
+    let mut seen = BitSet::new(grid.size().count() * 4);
 
     while let Some((pos, dir)) = next_position(grid, position, direction) {
         position = pos;
         direction = dir;
-        seen_positions.insert(position);
+        assert!(
+            seen.insert(cell_index(grid.size(), position) * 4 + direction_index(direction)),
+            "guard should leave the map"
+        );
+        states.push((position, direction));
     }
 
-    seen_positions
+    states
 }
 
-fn contains_loop(grid: &Grid<Cell>, mut position: Coord, mut direction: Direction) -> bool {
-    let mut seen_pos_dir = HashSet::<(Coord, Direction)>::new();
-    seen_pos_dir.insert((position, direction));
+/// For every cell and direction, the cell where the guard walking from it stops in front of an obstacle,
+/// `None` if it walks off the map.
+struct JumpTable {
+    size: Size,
+    stops: [Vec<Option<Coord>>; 4],
+}
 
-    while let Some((pos, dir)) = next_position(grid, position, direction) {
-        position = pos;
-        direction = dir;
-        if !seen_pos_dir.insert((position, direction)) {
-            return true;
+impl JumpTable {
+    fn new(grid: &Grid<Cell>) -> Self {
+        let size = grid.size();
+        let coords: Vec<Coord> = grid.coord_iter().collect();
+
+        let stops = DIRECTIONS.map(|direction| {
+            let mut stops = vec![None; size.count()];
+            // the cell ahead has to be resolved first: rows are ordered north to south, cells west to east.
+            let order: Box<dyn Iterator<Item = &Coord>> = match direction {
+                Direction::North | Direction::West => Box::new(coords.iter()),
+                Direction::East | Direction::South => Box::new(coords.iter().rev()),
+            };
+
+            for position in order {
+                let next = *position + offset(direction);
+                stops[cell_index(size, *position)] = match grid.get(next) {
+                    Some(Cell::Empty) => stops[cell_index(size, next)],
+                    Some(Cell::Blocked) => Some(*position),
+                    None => None,
+                };
+            }
+
+            stops
+        });
+
+        Self { size, stops }
+    }
+
+    /// Where the guard stops when walking from `position`, with an additional obstacle at `extra`.
+    fn jump(&self, position: Coord, direction: Direction, extra: Coord) -> Option<Coord> {
+        let stop = self.stops[direction_index(direction)][cell_index(self.size, position)];
+
+        let step = offset(direction);
+        let to_extra = extra - position;
+        let in_line = if step.x == 0 {
+            to_extra.x == 0
+        } else {
+            to_extra.y == 0
+        };
+        let distance = to_extra.x * step.x + to_extra.y * step.y;
+
+        if in_line && distance >= 1 {
+            let stop_distance = stop.map(|stop| {
+                let to_stop = stop - position;
+                to_stop.x * step.x + to_stop.y * step.y
+            });
+            if stop_distance.is_none_or(|stop_distance| distance <= stop_distance) {
+                return Some(position + step * (distance - 1));
+            }
         }
+
+        stop
     }
 
-    false
+    /// Whether the guard starting at `position` loops with an additional obstacle at `extra`.
+    fn loops(&self, mut position: Coord, mut direction: Direction, extra: Coord) -> bool {
+        let mut seen = BitSet::new(self.size.count() * 4);
+
+        loop {
+            if !seen.insert(cell_index(self.size, position) * 4 + direction_index(direction)) {
+                return true;
+            }
+            match self.jump(position, direction, extra) {
+                Some(stop) => {
+                    position = stop;
+                    direction = rotate_90(direction);
+                }
+                None => return false,
+            }
+        }
+    }
 }
 
 pub fn part_one(input: &str) -> Option<u32> {
@@ -118,7 +238,13 @@ pub fn part_one(input: &str) -> Option<u32> {
     let position = find_start_position(input).expect("must have start position");
     let direction = Direction::North;
 
-    Some(find_path(&grid, position, direction).len() as u32)
+    let mut visited = BitSet::new(grid.size().count());
+    let count = walk(&grid, position, direction)
+        .into_iter()
+        .filter(|(position, _)| visited.insert(cell_index(grid.size(), *position)))
+        .count();
+
+    Some(count as u32)
 }
 
 pub fn part_two(input: &str) -> Option<u32> {
@@ -126,17 +252,24 @@ pub fn part_two(input: &str) -> Option<u32> {
     let position = find_start_position(input).expect("must have start position");
     let direction = Direction::North;
 
-    let mut original_path = find_path(&grid, position, direction);
-    original_path.remove(&position); // Can't modify start position
-                                     //
+    // an obstacle only changes the path from the first visit of its cell on, so the guard can start
+    // from the state just before. The start position can't be blocked.
+    let states = walk(&grid, position, direction);
+    let mut visited = BitSet::new(grid.size().count());
+    visited.insert(cell_index(grid.size(), position));
+    let candidates: Vec<(Coord, (Coord, Direction))> = states
+        .windows(2)
+        .filter(|window| visited.insert(cell_index(grid.size(), window[1].0)))
+        .map(|window| (window[1].0, window[0]))
+        .collect();
+
+    let jumps = JumpTable::new(&grid);
+
     Some(
-        original_path
+        candidates
             .par_iter()
-            .filter(|pos| {
-                let mut grid = grid.clone();
-                *grid.get_mut(**pos).expect("should exist") = Cell::Blocked;
-
-                contains_loop(&grid, position, direction)
+            .filter(|(obstacle, (position, direction))| {
+                jumps.loops(*position, *direction, *obstacle)
             })
             .count() as u32,
     )
@@ -145,6 +278,7 @@ pub fn part_two(input: &str) -> Option<u32> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::collections::HashSet;
 
     #[test]
     fn test_part_one() {
@@ -157,4 +291,101 @@ mod tests {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
         assert_eq!(result, Some(6));
     }
+
+    /// The step by step simulation on a copy of the grid the jump table replaces.
+    fn contains_loop(grid: &Grid<Cell>, mut position: Coord, mut direction: Direction) -> bool {
+        let mut seen_pos_dir = HashSet::<(Coord, Direction)>::new();
+        seen_pos_dir.insert((position, direction));
+
+        while let Some((pos, dir)) = next_position(grid, position, direction) {
+            position = pos;
+            direction = dir;
+            if !seen_pos_dir.insert((position, direction)) {
+                return true;
+            }
+        }
+
+        false
+    }
+
+    fn part_two_reference(input: &str) -> u32 {
+        let grid = parse_grid(input);
+        let position = find_start_position(input).expect("must have start position");
+
+        let mut original_path: HashSet<Coord> = walk(&grid, position, Direction::North)
+            .into_iter()
+            .map(|(position, _)| position)
+            .collect();
+        original_path.remove(&position);
+
+        original_path
+            .iter()
+            .filter(|pos| {
+                let mut grid = grid.clone();
+                *grid.get_mut(**pos).expect("should exist") = Cell::Blocked;
+
+                contains_loop(&grid, position, Direction::North)
+            })
+            .count() as u32
+    }
+
+    #[test]
+    fn test_jumps_match_steps() {
+        let input = advent_of_code::template::read_file("examples", DAY);
+        let grid = parse_grid(&input);
+        let start = find_start_position(&input).unwrap();
+        let jumps = JumpTable::new(&grid);
+
+        for obstacle in grid.coord_iter() {
+            if obstacle == start || matches!(grid.get(obstacle), Some(Cell::Blocked)) {
+                continue;
+            }
+            let mut blocked = grid.clone();
+            *blocked.get_mut(obstacle).unwrap() = Cell::Blocked;
+
+            assert_eq!(
+                jumps.loops(start, Direction::North, obstacle),
+                contains_loop(&blocked, start, Direction::North),
+                "{obstacle:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_against_reference() {
+        let mut rng = fastrand::Rng::with_seed(0x2024_1206);
+        let mut tested = 0;
+
+        while tested < 200 {
+            let (width, height) = (rng.u64(0..12) + 3, rng.u64(0..12) + 3);
+            let start = (rng.u64(0..width), rng.u64(0..height));
+            let input: String = (0..height)
+                .map(|y| {
+                    let mut line: String = (0..width)
+                        .map(|x| match (x, y) {
+                            _ if (x, y) == start => '^',
+                            _ if rng.u64(0..6) == 0 => '#',
+                            _ => '.',
+                        })
+                        .collect();
+                    line.push('\n');
+                    line
+                })
+                .collect();
+
+            // the original walk has to leave the map.
+            let grid = parse_grid(&input);
+            let position = find_start_position(&input).unwrap();
+            if contains_loop(&grid, position, Direction::North) {
+                continue;
+            }
+
+            assert_eq!(
+                part_two(&input),
+                Some(part_two_reference(&input)),
+                "{input}"
+            );
+            tested += 1;
+        }
+    }
 }
@@ -1,3 +1,4 @@
+use advent_of_code::digits;
 use nom::{
     bytes::complete::tag,
     character::{self, complete::newline},
@@ -6,18 +7,23 @@ use nom::{
     IResult,
 };
 
-advent_of_code::solution!(7);
+advent_of_code::solution!(7, params = Params, debug = print_explanations);
+
+advent_of_code::params! {
+    /// Print the operators of every solvable equation after solving, e.g. `--param explain=true`.
+    explain: bool = false;
+}
 
 struct Equation {
-    result: i64,
-    operands: Vec<i64>,
+    result: u64,
+    operands: Vec<u64>,
 }
 
 fn parse_equation(input: &str) -> IResult<&str, Equation> {
     let (input, (result, operands)) = separated_pair(
-        character::complete::i64,
+        character::complete::u64,
         tag(": "),
-        separated_list1(tag(" "), character::complete::i64),
+        separated_list1(tag(" "), character::complete::u64),
     )(input)?;
 
     Ok((input, Equation { result, operands }))
@@ -30,84 +36,180 @@ fn parse_input(input: &str) -> Vec<Equation> {
     equations
 }
 
-fn valid_equation(result: i64, operands: &[i64]) -> bool {
-    match operands.len() {
-        0 => false,
-        1 => result == *operands.first().expect("can't be none"),
-        _ => {
-            let (operand, rest) = operands.split_last().expect("can't be none");
+/// A left-associative binary operator that can be undone, so equations can be solved from the result backwards.
+trait Operator {
+    fn symbol(&self) -> &'static str;
 
-            valid_equation(result - operand, rest)
-                || (result % operand == 0 && valid_equation(result / operand, rest))
-        }
+    fn apply(&self, left: u64, right: u64) -> Option<u64>;
+
+    /// The left operand `x` with `apply(x, right) == result`, `None` if there is none.
+    fn invert(&self, result: u64, right: u64) -> Option<u64>;
+
+    /// Whether `apply(x, right) == result` holds for every left operand `x`.
+    fn absorbs(&self, _result: u64, _right: u64) -> bool {
+        false
     }
 }
 
-fn valid_equation2(result: i64, operands: &[i64]) -> bool {
-    if result < 0 {
-        return false;
+struct Add;
+struct Multiply;
+struct Concatenate;
+
+impl Operator for Add {
+    fn symbol(&self) -> &'static str {
+        "+"
     }
 
-    match operands.len() {
-        0 => false,
-        1 => result == *operands.first().expect("can't be none"),
-        _ => {
-            let (operand, rest) = operands.split_last().expect("can't be none");
+    fn apply(&self, left: u64, right: u64) -> Option<u64> {
+        left.checked_add(right)
+    }
 
-            if valid_equation2(result - operand, rest)
-                || (result % operand == 0 && valid_equation2(result / operand, rest))
-            {
-                true
-            } else {
-                let res_str = result.to_string();
-                let op_str = operand.to_string();
+    fn invert(&self, result: u64, right: u64) -> Option<u64> {
+        result.checked_sub(right)
+    }
+}
 
-                if let Some(new_result) = res_str.strip_suffix(&op_str) {
-                    if new_result.is_empty() {
-                        false
-                    } else {
-                        valid_equation2(new_result.parse::<i64>().expect("should parse"), rest)
-                    }
-                } else {
-                    false
-                }
-            }
-        }
+impl Operator for Multiply {
+    fn symbol(&self) -> &'static str {
+        "*"
+    }
+
+    fn apply(&self, left: u64, right: u64) -> Option<u64> {
+        left.checked_mul(right)
+    }
+
+    fn invert(&self, result: u64, right: u64) -> Option<u64> {
+        // multiplying by zero loses the left operand, see `absorbs`.
+        (right != 0 && result.is_multiple_of(right)).then(|| result / right)
+    }
+
+    fn absorbs(&self, result: u64, right: u64) -> bool {
+        result == 0 && right == 0
     }
 }
 
-pub fn part_one(input: &str) -> Option<i64> {
-    let equations = parse_input(input);
+impl Operator for Concatenate {
+    fn symbol(&self) -> &'static str {
+        "||"
+    }
 
-    Some(
-        equations
-            .into_iter()
-            .filter_map(|equation| {
-                if valid_equation(equation.result, &equation.operands) {
-                    Some(equation.result)
-                } else {
-                    None
-                }
-            })
-            .sum(),
-    )
+    fn apply(&self, left: u64, right: u64) -> Option<u64> {
+        digits::concat(left, right)
+    }
+
+    fn invert(&self, result: u64, right: u64) -> Option<u64> {
+        digits::strip_suffix(result, right)
+    }
 }
 
-pub fn part_two(input: &str) -> Option<i64> {
-    let equations = parse_input(input);
+const PART_ONE_OPERATORS: &[&dyn Operator] = &[&Add, &Multiply];
+const PART_TWO_OPERATORS: &[&dyn Operator] = &[&Add, &Multiply, &Concatenate];
 
-    Some(
-        equations
-            .into_iter()
-            .filter_map(|equation| {
-                if valid_equation2(equation.result, &equation.operands) {
-                    Some(equation.result)
-                } else {
-                    None
-                }
-            })
-            .sum(),
-    )
+/// Finds operators between `operands` that evaluate to `result`, working backwards from the result
+/// and undoing the last operation. Returns the operator of every gap, from left to right.
+fn solve<'a>(
+    result: u64,
+    operands: &[u64],
+    operators: &[&'a dyn Operator],
+) -> Option<Vec<&'a dyn Operator>> {
+    let (operand, rest) = operands.split_last()?;
+    if rest.is_empty() {
+        return (result == *operand).then(Vec::new);
+    }
+
+    operators.iter().find_map(|operator| {
+        let mut assignment = if operator.absorbs(result, *operand) {
+            any_assignment(rest, operators)?
+        } else {
+            solve(operator.invert(result, *operand)?, rest, operators)?
+        };
+        assignment.push(*operator);
+        Some(assignment)
+    })
+}
+
+/// Finds any operators between `operands` that evaluate without overflow.
+fn any_assignment<'a>(
+    operands: &[u64],
+    operators: &[&'a dyn Operator],
+) -> Option<Vec<&'a dyn Operator>> {
+    fn extend<'a>(
+        value: u64,
+        operands: &[u64],
+        operators: &[&'a dyn Operator],
+    ) -> Option<Vec<&'a dyn Operator>> {
+        let Some((operand, rest)) = operands.split_first() else {
+            return Some(vec![]);
+        };
+
+        operators.iter().find_map(|operator| {
+            let mut assignment = extend(operator.apply(value, *operand)?, rest, operators)?;
+            assignment.insert(0, *operator);
+            Some(assignment)
+        })
+    }
+
+    let (first, rest) = operands.split_first()?;
+    extend(*first, rest, operators)
+}
+
+fn calibration_result(input: &str, operators: &[&dyn Operator]) -> u64 {
+    parse_input(input)
+        .into_iter()
+        .filter(|equation| solve(equation.result, &equation.operands, operators).is_some())
+        .map(|equation| equation.result)
+        .sum()
+}
+
+/// Evaluates the operands left to right with the given operators.
+fn evaluate(operands: &[u64], assignment: &[&dyn Operator]) -> Option<u64> {
+    let (first, rest) = operands.split_first()?;
+
+    rest.iter()
+        .zip(assignment)
+        .try_fold(*first, |value, (operand, operator)| {
+            operator.apply(value, *operand)
+        })
+}
+
+/// Renders a solved equation, e.g. `3267 = 81 * 40 + 27`.
+fn explain(equation: &Equation, assignment: &[&dyn Operator]) -> String {
+    debug_assert_eq!(
+        evaluate(&equation.operands, assignment),
+        Some(equation.result)
+    );
+    let mut explanation = format!("{} = {}", equation.result, equation.operands[0]);
+
+    for (operand, operator) in equation.operands[1..].iter().zip(assignment) {
+        explanation.push_str(&format!(" {} {operand}", operator.symbol()));
+    }
+
+    explanation
+}
+
+pub fn part_one(input: &str, _params: &Params) -> Option<u64> {
+    Some(calibration_result(input, PART_ONE_OPERATORS))
+}
+
+pub fn part_two(input: &str, _params: &Params) -> Option<u64> {
+    Some(calibration_result(input, PART_TWO_OPERATORS))
+}
+
+/// Prints how each solvable equation is solved with the operators of both parts, if enabled.
+fn print_explanations(input: &str, params: &Params) {
+    if !params.explain {
+        return;
+    }
+
+    let equations = parse_input(input);
+    for (part, operators) in [(1, PART_ONE_OPERATORS), (2, PART_TWO_OPERATORS)] {
+        println!("Part {part} equations:");
+        for equation in &equations {
+            if let Some(assignment) = solve(equation.result, &equation.operands, operators) {
+                println!("  {}", explain(equation, &assignment));
+            }
+        }
+    }
 }
 
 #[cfg(test)]
@@ -116,13 +218,96 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let (input, params) = advent_of_code::template::read_file_with_params("examples", DAY);
+        let result = part_one(&input, &params);
         assert_eq!(result, Some(3749));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let (input, params) = advent_of_code::template::read_file_with_params("examples", DAY);
+        let result = part_two(&input, &params);
         assert_eq!(result, Some(11387));
     }
+
+    fn explanations(operators: &[&dyn Operator]) -> Vec<String> {
+        parse_input(&advent_of_code::template::read_file("examples", DAY))
+            .iter()
+            .filter_map(|equation| {
+                let assignment = solve(equation.result, &equation.operands, operators)?;
+                assert_eq!(
+                    evaluate(&equation.operands, &assignment),
+                    Some(equation.result)
+                );
+                Some(explain(equation, &assignment))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_explains_part_one() {
+        assert_eq!(
+            explanations(PART_ONE_OPERATORS),
+            [
+                "190 = 10 * 19",
+                "3267 = 81 * 40 + 27",
+                "292 = 11 + 6 * 16 + 20"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_explains_part_two() {
+        let explanations = explanations(PART_TWO_OPERATORS);
+        assert_eq!(explanations.len(), 6);
+        assert!(explanations.contains(&"156 = 15 || 6".to_string()));
+        assert!(explanations.contains(&"192 = 17 || 8 + 14".to_string()));
+    }
+
+    /// Subtraction to check that custom operators plug into the solver.
+    struct Subtract;
+
+    impl Operator for Subtract {
+        fn symbol(&self) -> &'static str {
+            "-"
+        }
+
+        fn apply(&self, left: u64, right: u64) -> Option<u64> {
+            left.checked_sub(right)
+        }
+
+        fn invert(&self, result: u64, right: u64) -> Option<u64> {
+            result.checked_add(right)
+        }
+    }
+
+    #[test]
+    fn test_custom_operator() {
+        let equation = Equation {
+            result: 5,
+            operands: vec![10, 3, 2],
+        };
+        let operators: &[&dyn Operator] = &[&Add, &Subtract];
+        let assignment = solve(equation.result, &equation.operands, operators).unwrap();
+        assert_eq!(explain(&equation, &assignment), "5 = 10 - 3 - 2");
+        assert!(solve(4, &equation.operands, operators).is_none());
+    }
+
+    #[test]
+    fn test_multiply_by_zero() {
+        let equation = Equation {
+            result: 0,
+            operands: vec![5, 7, 0],
+        };
+        let assignment = solve(equation.result, &equation.operands, PART_ONE_OPERATORS).unwrap();
+        assert_eq!(explain(&equation, &assignment), "0 = 5 + 7 * 0");
+        assert!(solve(1, &equation.operands, PART_ONE_OPERATORS).is_none());
+
+        // every assignment of the leading operands overflows.
+        let equation = Equation {
+            result: 0,
+            operands: vec![u64::MAX, u64::MAX, 0],
+        };
+        assert!(solve(equation.result, &equation.operands, PART_ONE_OPERATORS).is_none());
+    }
 }
@@ -1,7 +1,7 @@
 use std::collections::HashSet;
 
+use advent_of_code::math::gcd;
 use grid_2d::{Coord, Grid, Size};
-use num::integer::gcd;
 
 advent_of_code::solution!(8);
 
@@ -33,7 +33,7 @@ fn antenna_locations(map: &Grid<char>) -> Vec<(Coord, char)> {
 
 fn points_on_line(p1: Coord, p2: Coord, dimensions: Size) -> Vec<Coord> {
     let distance = p2 - p1;
-    let step = distance / gcd(distance.x.abs(), distance.y.abs());
+    let step = distance / gcd(distance.x.into(), distance.y.into()) as i32;
     let mut points = vec![p1, p2];
 
     // Find points in one direction
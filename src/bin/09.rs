@@ -1,6 +1,15 @@
-use std::cmp::Ordering;
+use std::cmp::{Ordering, Reverse};
+use std::collections::BinaryHeap;
 
-advent_of_code::solution!(9);
+advent_of_code::solution!(9, params = Params, debug = print_layouts);
+
+advent_of_code::params! {
+    /// Print the disk before and after compacting, e.g. `--param show_layout=true`.
+    show_layout: bool = false;
+}
+
+/// Largest size of a file or free span, sizes are single digits.
+const MAX_SIZE: usize = 9;
 
 #[derive(Debug, Copy, Clone, PartialEq)]
 enum BlockId {
@@ -91,99 +100,232 @@ fn compact_by_block(mut memory: Vec<MemoryBlock>) -> Vec<MemoryBlock> {
     compacted
 }
 
-fn highest_id(memory: &[MemoryBlock]) -> Option<u32> {
-    let index = memory
-        .iter()
-        .rev()
-        .position(|block| block.id != BlockId::Empty)?;
+/// A file, or part of a file, at its position on the disk.
+#[derive(Debug, Copy, Clone)]
+struct File {
+    id: u32,
+    start: u64,
+    size: u32,
+}
 
-    if let Some(BlockId::Id(id)) = Some(memory.get(memory.len() - index - 1)?.id) {
-        Some(id)
-    } else {
-        None
+fn files(memory: &[MemoryBlock]) -> Vec<File> {
+    let mut start = 0;
+    let mut files = Vec::new();
+
+    for block in memory {
+        if let BlockId::Id(id) = block.id {
+            files.push(File {
+                id,
+                start,
+                size: block.size,
+            });
+        }
+        start += u64::from(block.size);
     }
+
+    files
 }
 
-fn compact_by_file(mut memory: Vec<MemoryBlock>) -> Vec<MemoryBlock> {
-    let max_id = highest_id(&memory).unwrap();
+/// Moves every file, highest id first, to the leftmost free span it fits in.
+/// Free spans are kept in one min-heap of start positions per span size.
+fn compact_by_file(memory: &[MemoryBlock]) -> Vec<File> {
+    let mut free: [BinaryHeap<Reverse<u64>>; MAX_SIZE + 1] = Default::default();
+    let mut start = 0;
 
-    for id in (0..=max_id).rev() {
-        let move_from = memory
-            .iter()
-            .position(|block| block.id == BlockId::Id(id))
-            .unwrap();
-
-        let from = memory[move_from];
-
-        for move_to in 0..move_from {
-            if memory[move_to].id == BlockId::Empty {
-                match memory[move_to].size.cmp(&from.size) {
-                    Ordering::Equal => {
-                        memory[move_from] = memory[move_to];
-                        memory[move_to] = from;
-                        break;
-                    }
-                    Ordering::Greater => {
-                        memory[move_from].id = BlockId::Empty;
-
-                        memory[move_to].size -= from.size;
-                        memory.insert(move_to, from);
-                        break;
-                    }
-                    Ordering::Less => (),
-                }
+    for block in memory {
+        if block.id == BlockId::Empty && block.size > 0 {
+            free[block.size as usize].push(Reverse(start));
+        }
+        start += u64::from(block.size);
+    }
+
+    let mut files = files(memory);
+
+    for file in files.iter_mut().rev() {
+        let leftmost = (file.size as usize..=MAX_SIZE)
+            .filter_map(|size| free[size].peek().map(|Reverse(start)| (*start, size)))
+            .filter(|(start, _)| *start < file.start)
+            .min();
+
+        if let Some((start, size)) = leftmost {
+            free[size].pop();
+            file.start = start;
+
+            // the vacated space is right of all remaining files, only the rest of the span stays useful.
+            let rest = size - file.size as usize;
+            if rest > 0 {
+                free[rest].push(Reverse(start + u64::from(file.size)));
             }
         }
     }
 
-    memory
+    files
 }
 
-fn calculate_checksum(memory: &[MemoryBlock]) -> u64 {
-    let mut checksum: u64 = 0;
-    let mut index = 0;
+fn calculate_checksum(files: &[File]) -> u64 {
+    files
+        .iter()
+        .map(|file| {
+            let size = u64::from(file.size);
+            // sum of the positions start..start + size
+            let positions = file.start * size + size * size.saturating_sub(1) / 2;
+            u64::from(file.id) * positions
+        })
+        .sum()
+}
 
-    memory.iter().for_each(|block| {
-        for _ in 0..block.size {
-            if let BlockId::Id(id) = block.id {
-                checksum += index * id as u64
-            }
-            index += 1;
+/// Renders the disk like the puzzle does, e.g. `00...111...2...`. Ids above 9 wrap around.
+fn layout(files: &[File], len: u64) -> String {
+    let mut disk = vec!['.'; len as usize];
+
+    for file in files {
+        let digit = char::from_digit(file.id % 10, 10).expect("is a digit");
+        for position in file.start..file.start + u64::from(file.size) {
+            disk[position as usize] = digit;
         }
-    });
+    }
 
-    checksum
+    disk.into_iter().collect()
 }
 
-pub fn part_one(input: &str) -> Option<u64> {
-    let blocks = parse_input(input);
+fn disk_len(memory: &[MemoryBlock]) -> u64 {
+    memory.iter().map(|block| u64::from(block.size)).sum()
+}
 
-    let compacted = compact_by_block(blocks);
+pub fn part_one(input: &str, _params: &Params) -> Option<u64> {
+    let blocks = parse_input(input);
 
-    Some(calculate_checksum(&compacted))
+    Some(calculate_checksum(&files(&compact_by_block(blocks))))
 }
 
-pub fn part_two(input: &str) -> Option<u64> {
+pub fn part_two(input: &str, _params: &Params) -> Option<u64> {
     let blocks = parse_input(input);
 
-    let compacted = compact_by_file(blocks);
+    Some(calculate_checksum(&compact_by_file(&blocks)))
+}
+
+/// Prints the disk before compacting and after compacting it for each part, if enabled.
+fn print_layouts(input: &str, params: &Params) {
+    if !params.show_layout {
+        return;
+    }
 
-    Some(calculate_checksum(&compacted))
+    let blocks = parse_input(input);
+    let len = disk_len(&blocks);
+    println!("Disk:   {}", layout(&files(&blocks), len));
+    println!(
+        "Part 1: {}",
+        layout(&files(&compact_by_block(blocks.clone())), len)
+    );
+    println!("Part 2: {}", layout(&compact_by_file(&blocks), len));
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use advent_of_code::template::ParamSet;
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let (input, params) = advent_of_code::template::read_file_with_params("examples", DAY);
+        let result = part_one(&input, &params);
         assert_eq!(result, Some(1928));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let (input, params) = advent_of_code::template::read_file_with_params("examples", DAY);
+        let result = part_two(&input, &params);
         assert_eq!(result, Some(2858));
     }
+
+    #[test]
+    fn test_layouts() {
+        let blocks = parse_input(&advent_of_code::template::read_file("examples", DAY));
+        let len = disk_len(&blocks);
+
+        assert_eq!(
+            layout(&files(&blocks), len),
+            "00...111...2...333.44.5555.6666.777.888899"
+        );
+        assert_eq!(
+            layout(&files(&compact_by_block(blocks.clone())), len),
+            "0099811188827773336446555566.............."
+        );
+        assert_eq!(
+            layout(&compact_by_file(&blocks), len),
+            "00992111777.44.333....5555.6666.....8888.."
+        );
+    }
+
+    #[test]
+    fn test_small_disk() {
+        let blocks = parse_input("12345");
+        let len = disk_len(&blocks);
+        assert_eq!(layout(&files(&blocks), len), "0..111....22222");
+        assert_eq!(
+            layout(&files(&compact_by_block(blocks.clone())), len),
+            "022111222......"
+        );
+        // no free span fits the files.
+        assert_eq!(layout(&compact_by_file(&blocks), len), "0..111....22222");
+    }
+
+    /// Moves files block by block, the straightforward way.
+    fn compact_by_file_naive(memory: &[MemoryBlock]) -> Vec<Option<u32>> {
+        let mut disk: Vec<Option<u32>> = memory
+            .iter()
+            .flat_map(|block| {
+                let id = match block.id {
+                    BlockId::Id(id) => Some(id),
+                    BlockId::Empty => None,
+                };
+                std::iter::repeat_n(id, block.size as usize)
+            })
+            .collect();
+
+        for file in files(memory).iter().rev() {
+            let (start, size) = (file.start as usize, file.size as usize);
+            let target = (0..start).find(|&i| disk[i..i + size].iter().all(Option::is_none));
+
+            if let Some(target) = target {
+                for offset in 0..size {
+                    disk[target + offset] = Some(file.id);
+                    disk[start + offset] = None;
+                }
+            }
+        }
+
+        disk
+    }
+
+    #[test]
+    fn test_against_naive() {
+        let mut rng = fastrand::Rng::with_seed(0x2024_1209);
+        let params = Params::example();
+
+        for _ in 0..200 {
+            let len = rng.usize(1..=40);
+            let input: String = (0..len)
+                .map(|i| {
+                    // files have at least one block.
+                    let size = if i % 2 == 0 {
+                        rng.u64(1..=9)
+                    } else {
+                        rng.u64(0..10)
+                    };
+                    char::from_digit(size as u32, 10).unwrap()
+                })
+                .collect();
+
+            let blocks = parse_input(&input);
+            let expected: u64 = compact_by_file_naive(&blocks)
+                .iter()
+                .enumerate()
+                .filter_map(|(position, id)| Some(position as u64 * u64::from((*id)?)))
+                .sum();
+
+            assert_eq!(part_two(&input, &params), Some(expected), "{input}");
+        }
+    }
 }
@@ -1,5 +1,4 @@
-use std::collections::HashSet;
-
+use advent_of_code::grid_dag::GridDag;
 use grid_2d::{Coord, Grid, Size};
 
 advent_of_code::solution!(10);
@@ -22,72 +21,41 @@ fn parse_grid(input: &str) -> Map {
     )
 }
 
-fn adjacent_cells(position: Coord) -> [Coord; 4] {
-    [
-        position + Coord::new(-1, 0),
-        position + Coord::new(1, 0),
-        position + Coord::new(0, -1),
-        position + Coord::new(0, 1),
-    ]
-}
-
 fn get_trailheads(map: &Map) -> Vec<Coord> {
     map.coord_iter()
         .filter(|p| *map.get(*p).expect("shouldn't fail") == 0)
         .collect()
 }
 
-fn find_unique_trails(p: Coord, map: &Map, mut path: Vec<Coord>) -> HashSet<Vec<Coord>> {
-    let mut paths = HashSet::new();
-    let elevation = map.get(p).expect("must exist");
-    path.push(p);
-
-    if *elevation == 9 {
-        paths.insert(path);
-    } else {
-        for adjacent in adjacent_cells(p) {
-            if let Some(adj_elevation) = map.get(adjacent) {
-                if *adj_elevation == elevation + 1 {
-                    paths.extend(find_unique_trails(adjacent, map, path.clone()));
-                }
-            }
-        }
-    }
-
-    paths
-}
-
-fn calc_trail_score(trailhead: Coord, map: &Map) -> u32 {
-    let trail_ends: HashSet<Coord> = find_unique_trails(trailhead, map, Vec::new())
-        .into_iter()
-        .map(|path| *path.last().unwrap())
-        .collect();
-
-    trail_ends.len() as u32
+/// Trails go up by exactly one per step, so they form a directed acyclic graph.
+fn trail_graph(map: &Map) -> GridDag {
+    GridDag::new(map, |from, to| *to == from + 1).expect("trails always go up")
 }
 
-fn calc_trail_rating(trailhead: Coord, map: &Map) -> u32 {
-    find_unique_trails(trailhead, map, Vec::new()).len() as u32
+fn is_summit(map: &Map, position: Coord) -> bool {
+    map.get(position) == Some(&9)
 }
 
 pub fn part_one(input: &str) -> Option<u32> {
     let map = parse_grid(input);
+    let summits = trail_graph(&map).reachable_targets(|p| is_summit(&map, p));
 
     Some(
         get_trailheads(&map)
             .into_iter()
-            .map(|trailhead| calc_trail_score(trailhead, &map))
+            .map(|trailhead| summits.count(trailhead) as u32)
             .sum(),
     )
 }
 
 pub fn part_two(input: &str) -> Option<u32> {
     let map = parse_grid(input);
+    let ratings = trail_graph(&map).path_counts(|p| is_summit(&map, p));
 
     Some(
         get_trailheads(&map)
             .into_iter()
-            .map(|trailhead| calc_trail_rating(trailhead, &map))
+            .map(|trailhead| *ratings.get_checked(trailhead) as u32)
             .sum(),
     )
 }
@@ -107,4 +75,24 @@ mod tests {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
         assert_eq!(result, Some(81));
     }
+
+    #[test]
+    fn test_trailhead_scores() {
+        let map = parse_grid(&advent_of_code::template::read_file("examples", DAY));
+        let graph = trail_graph(&map);
+        let summits = graph.reachable_targets(|p| is_summit(&map, p));
+        let ratings = graph.path_counts(|p| is_summit(&map, p));
+
+        let scores: Vec<usize> = get_trailheads(&map)
+            .into_iter()
+            .map(|trailhead| summits.count(trailhead))
+            .collect();
+        assert_eq!(scores, [5, 6, 5, 3, 1, 3, 5, 3, 5]);
+
+        let ratings: Vec<u64> = get_trailheads(&map)
+            .into_iter()
+            .map(|trailhead| *ratings.get_checked(trailhead))
+            .collect();
+        assert_eq!(ratings, [20, 24, 10, 4, 1, 4, 5, 8, 5]);
+    }
 }
@@ -1,8 +1,19 @@
 use std::collections::HashMap;
 
+use advent_of_code::{digits, template::Solution};
 use nom::{bytes::complete::tag, character, multi::separated_list1, IResult};
+use num::{BigUint, CheckedAdd, Zero};
 
-advent_of_code::solution!(11);
+advent_of_code::solution!(11, Day11);
+
+advent_of_code::params! {
+    /// Number of blinks in part one, the example walks through the first six.
+    blinks_one: u32 = 25, example = 6;
+    /// Number of blinks in part two.
+    blinks_two: u32 = 75;
+}
+
+pub struct Day11;
 
 fn parse_stones(input: &str) -> IResult<&str, Vec<u64>> {
     separated_list1(tag(" "), character::complete::u64)(input)
@@ -19,73 +30,223 @@ fn parse_input(input: &str) -> HashMap<u64, u64> {
     map
 }
 
-fn num_decimal_digits(n: u64) -> u32 {
-    n.to_string().len() as u32
-}
-
-fn apply_blink_rules(stone: u64) -> (u64, Option<u64>) {
+/// The stones replacing `stone`, `None` if the engraved number outgrows `u64`.
+fn apply_blink_rules(stone: u64) -> Option<(u64, Option<u64>)> {
     if stone == 0 {
-        return (1, None);
+        return Some((1, None));
     }
 
-    let num_digits = num_decimal_digits(stone);
-    if num_digits % 2 == 0 {
-        let divisor = 10u64.pow(num_digits / 2);
-
-        return (stone / divisor, Some(stone % divisor));
+    if let Some((left, right)) = digits::split_in_half(stone) {
+        return Some((left, Some(right)));
     }
 
-    (stone * 2024, None)
+    Some((stone.checked_mul(2024)?, None))
 }
 
-fn evaluate_blink(old_stones: HashMap<u64, u64>) -> HashMap<u64, u64> {
-    let mut new_stones = HashMap::new();
+/// Why stones could not be counted.
+#[derive(Debug, PartialEq, Eq)]
+enum CountError {
+    /// The number of stones does not fit into the count type.
+    CountOverflow,
+    /// Multiplying this stone by 2024 does not fit into `u64`.
+    StoneOverflow(u64),
+}
 
-    for (stone, count) in old_stones {
-        let (left, right) = apply_blink_rules(stone);
+/// Memoised expansion of stones: every distinct stone gets an index and its successors are computed once.
+/// Stones quickly fall into a small closed set, so this stays small for any number of blinks.
+#[derive(Default, Clone)]
+pub struct StoneGraph {
+    indices: HashMap<u64, usize>,
+    stones: Vec<u64>,
+    successors: Vec<Option<(usize, Option<usize>)>>,
+}
+
+impl StoneGraph {
+    fn index(&mut self, stone: u64) -> usize {
+        *self.indices.entry(stone).or_insert_with(|| {
+            self.stones.push(stone);
+            self.successors.push(None);
+            self.stones.len() - 1
+        })
+    }
 
-        *new_stones.entry(left).or_insert(0) += count;
-        if let Some(right) = right {
-            *new_stones.entry(right).or_insert(0) += count;
+    fn successors(&mut self, index: usize) -> Result<(usize, Option<usize>), CountError> {
+        if let Some(successors) = self.successors[index] {
+            return Ok(successors);
         }
+
+        let stone = self.stones[index];
+        let (left, right) = apply_blink_rules(stone).ok_or(CountError::StoneOverflow(stone))?;
+        let successors = (self.index(left), right.map(|right| self.index(right)));
+        self.successors[index] = Some(successors);
+
+        Ok(successors)
     }
 
-    new_stones
-}
+    fn len(&self) -> usize {
+        self.stones.len()
+    }
 
-pub fn part_one(input: &str) -> Option<u64> {
-    let mut stones = parse_input(input);
+    /// Number of stones after `blinks`.
+    fn count<C>(&mut self, stones: &HashMap<u64, u64>, blinks: u32) -> Result<C, CountError>
+    where
+        C: Clone + Zero + CheckedAdd + From<u64>,
+    {
+        let mut counts: Vec<C> = vec![];
+        for (&stone, &count) in stones {
+            let index = self.index(stone);
+            counts.resize(self.len(), C::zero());
+            counts[index] = add(&counts[index], &C::from(count))?;
+        }
+
+        let mut next: Vec<C> = vec![];
+        for _ in 0..blinks {
+            next.clear();
+            for (index, count) in counts.iter().enumerate() {
+                if count.is_zero() {
+                    continue;
+                }
+
+                let (left, right) = self.successors(index)?;
+                next.resize(self.len(), C::zero());
+                next[left] = add(&next[left], count)?;
+                if let Some(right) = right {
+                    next[right] = add(&next[right], count)?;
+                }
+            }
+            std::mem::swap(&mut counts, &mut next);
+        }
+
+        counts
+            .iter()
+            .try_fold(C::zero(), |total, count| add(&total, count))
+    }
 
-    for _ in 0..25 {
-        stones = evaluate_blink(stones);
+    /// Number of stones after `blinks`, counting with `u128` and falling back to big integers on overflow.
+    fn count_any(
+        &mut self,
+        stones: &HashMap<u64, u64>,
+        blinks: u32,
+    ) -> Result<BigUint, CountError> {
+        match self.count::<u128>(stones, blinks) {
+            Ok(count) => Ok(BigUint::from(count)),
+            Err(CountError::CountOverflow) => self.count::<BigUint>(stones, blinks),
+            Err(e) => Err(e),
+        }
     }
+}
 
-    Some(stones.values().sum())
+fn add<C: CheckedAdd>(lhs: &C, rhs: &C) -> Result<C, CountError> {
+    lhs.checked_add(rhs).ok_or(CountError::CountOverflow)
 }
 
-pub fn part_two(input: &str) -> Option<u64> {
-    let mut stones = parse_input(input);
+fn report(count: Result<BigUint, CountError>) -> Option<BigUint> {
+    count
+        .inspect_err(|e| {
+            if let CountError::StoneOverflow(stone) = e {
+                eprintln!("Stone {stone} grows beyond u64 when multiplied by 2024.");
+            }
+        })
+        .ok()
+}
+
+impl Solution for Day11 {
+    type Parsed = HashMap<u64, u64>;
+    type Params = Params;
+    /// The stone expansions of part one, part two reuses them.
+    type Carry = StoneGraph;
+    type Answer1 = BigUint;
+    type Answer2 = BigUint;
+
+    fn parse(input: &str) -> Self::Parsed {
+        parse_input(input)
+    }
 
-    for _ in 0..75 {
-        stones = evaluate_blink(stones);
+    fn part_one(
+        stones: &Self::Parsed,
+        params: &Params,
+        graph: &mut Self::Carry,
+    ) -> Option<BigUint> {
+        report(graph.count_any(stones, params.blinks_one))
     }
 
-    Some(stones.values().sum())
+    fn part_two(stones: &Self::Parsed, params: &Params, graph: &Self::Carry) -> Option<BigUint> {
+        report(graph.clone().count_any(stones, params.blinks_two))
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use advent_of_code::template::{
+        read_file_with_params, solve_part_one, solve_part_two, ParamSet,
+    };
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(55312));
+        let (input, params) = read_file_with_params::<Params>("examples", DAY);
+        let result = solve_part_one::<Day11>(&input, &params);
+        assert_eq!(result, Some(BigUint::from(22u64)));
+    }
+
+    #[test]
+    fn test_part_one_25_blinks() {
+        let (input, params) = read_file_with_params::<Params>("examples", DAY);
+        let params = Params {
+            blinks_one: 25,
+            ..params
+        };
+        let result = solve_part_one::<Day11>(&input, &params);
+        assert_eq!(result, Some(BigUint::from(55312u64)));
     }
 
     #[test]
     fn test_part_two() {
-        // let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        // assert_eq!(result, None);
+        let (input, params) = read_file_with_params::<Params>("examples", DAY);
+        let result = solve_part_two::<Day11>(&input, &params);
+        assert_eq!(result, Some(BigUint::from(65601038650482u64)));
+    }
+
+    #[test]
+    fn test_counts_agree() {
+        let stones = parse_input(&advent_of_code::template::read_file("examples", DAY));
+        let mut graph = StoneGraph::default();
+        let small = graph.count::<u128>(&stones, 150).map(BigUint::from);
+        let big = graph.count::<BigUint>(&stones, 150);
+        assert!(big.is_ok());
+        assert_eq!(small, big);
+    }
+
+    #[test]
+    fn test_thousands_of_blinks() {
+        let stones = parse_input(&advent_of_code::template::read_file("examples", DAY));
+        let mut graph = StoneGraph::default();
+        assert_eq!(
+            graph.count::<u128>(&stones, 2_000),
+            Err(CountError::CountOverflow)
+        );
+
+        let count = graph.count_any(&stones, 2_000).unwrap();
+        assert!(count.bits() > 128);
+        // every stone has at most two successors.
+        assert!(graph.count_any(&stones, 2_001).unwrap() <= &count * 2u32);
+    }
+
+    #[test]
+    fn test_large_stones() {
+        // 19 digits, multiplying by 2024 overflows u64.
+        let stone = 1_000_000_000_000_000_001;
+        let stones = HashMap::from([(stone, 1)]);
+        let mut graph = StoneGraph::default();
+        assert_eq!(
+            graph.count::<u128>(&stones, 1),
+            Err(CountError::StoneOverflow(stone))
+        );
+        assert_eq!(graph.count_any(&stones, 0), Ok(BigUint::from(1u64)));
+        let params = Params::example();
+        assert_eq!(
+            solve_part_one::<Day11>("1000000000000000001 0", &params),
+            None
+        );
     }
 }
@@ -1,10 +1,11 @@
+use advent_of_code::math::{self, LinearSolution};
 use nom::{
     bytes::complete::tag,
     character::complete::{anychar, digit1, newline},
     multi::separated_list1,
     IResult,
 };
-use num::integer::lcm;
+use num::Integer;
 
 advent_of_code::solution!(13);
 
@@ -64,67 +65,116 @@ fn parse_machines(input: &str) -> Vec<Machine> {
     machines
 }
 
-fn prize_min_presses(machine: &Machine) -> Option<i64> {
-    // Solve for B
-    let lcm = lcm(machine.a_x, machine.a_y);
-    let x_factor = lcm / machine.a_x;
-    let y_factor = lcm / machine.a_y;
+const A_COST: i64 = 3;
+const B_COST: i64 = 1;
+const PRIZE_OFFSET: i64 = 10_000_000_000_000;
 
-    let b_num = y_factor * machine.prize_y - x_factor * machine.prize_x;
-    let b_den = y_factor * machine.b_y - x_factor * machine.b_x;
-
-    if b_num % b_den != 0 {
-        return None; // Non-integral solution
+/// Minimal cost of `A_COST * a + B_COST * b` over non-negative integers with `p * a + q * b = r`.
+/// `p` and `q` must not both be zero. `None` if intermediate values overflow.
+fn min_cost_on_line(p: i64, q: i64, r: i64) -> Option<i64> {
+    if p == 0 {
+        // `a` does not move the claw, never press it.
+        return (r % q == 0 && r / q >= 0).then(|| B_COST.checked_mul(r / q))?;
+    }
+    if q == 0 {
+        return (r % p == 0 && r / p >= 0).then(|| A_COST.checked_mul(r / p))?;
     }
 
-    let b = b_num / b_den;
-
-    if b < 0 {
-        return None; // Negative solution
+    let (gcd, x, y) = math::checked_ext_gcd(p, q).ok()?;
+    if r % gcd != 0 {
+        return None;
     }
 
-    // Solve for A
-    let a_num = machine.prize_x - machine.b_x * b;
-    let a_den = machine.a_x;
+    // all solutions: a = a0 + k * s, b = b0 - k * t.
+    let (a0, b0) = (x.checked_mul(r / gcd)?, y.checked_mul(r / gcd)?);
+    let (s, t) = (q / gcd, p / gcd);
 
-    if a_num % a_den != 0 {
-        return None; // Non-integral solution
+    let mut lo: Option<i64> = None;
+    let mut hi: Option<i64> = None;
+    let mut bound = |value: i64, step: i64| {
+        // value + k * step >= 0
+        if step > 0 {
+            let k = -Integer::div_floor(&value, &step);
+            lo = Some(lo.map_or(k, |lo| lo.max(k)));
+        } else {
+            let k = Integer::div_floor(&value, &-step);
+            hi = Some(hi.map_or(k, |hi| hi.min(k)));
+        }
+    };
+    bound(a0, s);
+    bound(b0, -t);
+
+    if let (Some(lo), Some(hi)) = (lo, hi) {
+        if lo > hi {
+            return None;
+        }
     }
 
-    let a = a_num / a_den;
+    // the cost is linear in `k`, so the minimum is at one end of the feasible range.
+    let slope = A_COST.checked_mul(s)?.checked_sub(B_COST.checked_mul(t)?)?;
+    let k = if slope > 0 { lo? } else { hi.or(lo)? };
+    let a = a0.checked_add(k.checked_mul(s)?)?;
+    let b = b0.checked_sub(k.checked_mul(t)?)?;
 
-    if a < 0 {
-        return None; // Negative solution
-    }
+    tokens(a, b)
+}
 
-    Some(a * 3 + b)
+/// Cost of pressing `a` and `b`, `None` on overflow.
+fn tokens(a: i64, b: i64) -> Option<i64> {
+    A_COST.checked_mul(a)?.checked_add(B_COST.checked_mul(b)?)
 }
 
-pub fn part_one(input: &str) -> Option<i64> {
-    let machines = parse_machines(input);
+/// Minimal number of tokens to win the prize moved by `offset`, `None` if it cannot be won.
+/// Prizes beyond `i64::MAX` are treated as unreachable.
+fn prize_min_cost(machine: &Machine, offset: i64) -> Option<i64> {
+    let prize_x = machine.prize_x.checked_add(offset)?;
+    let prize_y = machine.prize_y.checked_add(offset)?;
 
-    Some(
-        machines
-            .into_iter()
-            .filter_map(|machine| prize_min_presses(&machine))
-            .sum(),
+    let solution = math::checked_solve_2x2(
+        [[machine.a_x, machine.b_x], [machine.a_y, machine.b_y]],
+        [prize_x, prize_y],
     )
+    .ok()?;
+
+    let cost = match solution {
+        LinearSolution::Unique(_) => {
+            let [a, b] = solution.integer_solution()?[..] else {
+                unreachable!("a 2x2 system has two unknowns");
+            };
+            if a < 0 || b < 0 {
+                return None; // Negative solution
+            }
+            tokens(a, b)?
+        }
+        // collinear buttons with the prize on their line.
+        LinearSolution::Infinite => {
+            if machine.a_x != 0 || machine.b_x != 0 {
+                min_cost_on_line(machine.a_x, machine.b_x, prize_x)?
+            } else if machine.a_y != 0 || machine.b_y != 0 {
+                min_cost_on_line(machine.a_y, machine.b_y, prize_y)?
+            } else {
+                0
+            }
+        }
+        LinearSolution::None => return None,
+    };
+
+    Some(cost)
+}
+
+fn total_cost(machines: &[Machine], offset: i64) -> Option<i64> {
+    machines
+        .iter()
+        .filter_map(|machine| prize_min_cost(machine, offset))
+        .try_fold(0_i64, i64::checked_add)
+}
+
+pub fn part_one(input: &str) -> Option<i64> {
+    total_cost(&parse_machines(input), 0)
 }
 
 pub fn part_two(input: &str) -> Option<i64> {
-    let mut machines = parse_machines(input);
-
-    machines.iter_mut().for_each(|machine| {
-        machine.prize_x += 10_000_000_000_000;
-        machine.prize_y += 10_000_000_000_000;
-    });
-
-    Some(
-        machines
-            .into_iter()
-            .filter_map(|machine| prize_min_presses(&machine))
-            .sum(),
-    )
+    total_cost(&parse_machines(input), PRIZE_OFFSET)
 }
 
 #[cfg(test)]
@@ -142,4 +192,96 @@ mod tests {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
         assert_eq!(result, Some(875318608908));
     }
+
+    fn brute_force(machine: &Machine) -> Option<i64> {
+        (0..=200)
+            .flat_map(|a| (0..=200).map(move |b| (a, b)))
+            .filter(|(a, b)| {
+                a * machine.a_x + b * machine.b_x == machine.prize_x
+                    && a * machine.a_y + b * machine.b_y == machine.prize_y
+            })
+            .map(|(a, b)| 3 * a + b)
+            .min()
+    }
+
+    fn machine(a: (i64, i64), b: (i64, i64), prize: (i64, i64)) -> Machine {
+        Machine {
+            a_x: a.0,
+            a_y: a.1,
+            b_x: b.0,
+            b_y: b.1,
+            prize_x: prize.0,
+            prize_y: prize.1,
+        }
+    }
+
+    #[test]
+    fn test_collinear_buttons() {
+        // B is twice A, pressing B once is cheaper than pressing A twice.
+        assert_eq!(prize_min_cost(&machine((1, 1), (2, 2), (5, 5)), 0), Some(5));
+        // A is three times B, pressing A is as cheap as pressing B three times.
+        assert_eq!(
+            prize_min_cost(&machine((3, 6), (1, 2), (7, 14)), 0),
+            Some(7)
+        );
+        // the prize is off the line of the buttons.
+        assert_eq!(prize_min_cost(&machine((1, 1), (2, 2), (5, 6)), 0), None);
+        // gcd of the buttons does not divide the prize.
+        assert_eq!(prize_min_cost(&machine((4, 4), (6, 6), (7, 7)), 0), None);
+    }
+
+    #[test]
+    fn test_zero_components() {
+        assert_eq!(prize_min_cost(&machine((0, 0), (0, 0), (0, 0)), 0), Some(0));
+        assert_eq!(prize_min_cost(&machine((0, 0), (0, 0), (1, 0)), 0), None);
+        assert_eq!(
+            prize_min_cost(&machine((0, 3), (2, 0), (4, 9)), 0),
+            Some(11)
+        );
+        assert_eq!(prize_min_cost(&machine((0, 2), (0, 3), (0, 7)), 0), Some(7));
+    }
+
+    #[test]
+    fn test_large_offsets() {
+        let collinear = machine((1, 1), (2, 2), (0, 0));
+        assert_eq!(
+            prize_min_cost(&collinear, PRIZE_OFFSET),
+            Some(PRIZE_OFFSET / 2)
+        );
+        // pressing A `i64::MAX` times costs more than fits into the answer.
+        let only_a = machine((1, 1), (0, 0), (0, 0));
+        assert_eq!(prize_min_cost(&only_a, i64::MAX), None);
+    }
+
+    #[test]
+    fn test_against_brute_force() {
+        let mut rng = fastrand::Rng::with_seed(0x2024_1213);
+
+        for _ in 0..1_000 {
+            // small coefficients make zero components and collinear buttons likely.
+            let a = (rng.i64(0..5), rng.i64(0..5));
+            let b = match rng.i64(0..3) {
+                0 => {
+                    let factor = rng.i64(0..3) + 1;
+                    (a.0 * factor, a.1 * factor)
+                }
+                _ => (rng.i64(0..5), rng.i64(0..5)),
+            };
+            let (presses_a, presses_b) = (rng.i64(0..20), rng.i64(0..20));
+            let prize = match rng.i64(0..4) {
+                0 => (rng.i64(0..40), rng.i64(0..40)),
+                _ => (
+                    presses_a * a.0 + presses_b * b.0,
+                    presses_a * a.1 + presses_b * b.1,
+                ),
+            };
+
+            let machine = machine(a, b, prize);
+            assert_eq!(
+                prize_min_cost(&machine, 0),
+                brute_force(&machine),
+                "{machine:?}"
+            );
+        }
+    }
 }
@@ -0,0 +1,121 @@
+//! Decimal digit manipulation of unsigned integers without going through strings.
+
+/// `10^exp`, panics if the result does not fit into `u64`.
+pub const fn pow10(exp: u32) -> u64 {
+    10u64.pow(exp)
+}
+
+/// Number of decimal digits of `n`, `0` has one digit.
+pub const fn digit_count(n: u64) -> u32 {
+    match n.checked_ilog10() {
+        Some(log) => log + 1,
+        None => 1,
+    }
+}
+
+/// Splits off the lowest `digits` digits, e.g. `split_at_digit(12345, 2) == (123, 45)`.
+pub const fn split_at_digit(n: u64, digits: u32) -> (u64, u64) {
+    if digits >= 20 {
+        return (0, n);
+    }
+    let divisor = pow10(digits);
+    (n / divisor, n % divisor)
+}
+
+/// Splits a number with an even digit count into its halves, e.g. `1000` into `(10, 0)`.
+pub const fn split_in_half(n: u64) -> Option<(u64, u64)> {
+    let digits = digit_count(n);
+    if digits.is_multiple_of(2) {
+        Some(split_at_digit(n, digits / 2))
+    } else {
+        None
+    }
+}
+
+/// Concatenates the digits of two numbers, e.g. `concat(12, 345) == Some(12345)`. `None` on overflow.
+pub const fn concat(a: u64, b: u64) -> Option<u64> {
+    let Some(shift) = 10u64.checked_pow(digit_count(b)) else {
+        return None;
+    };
+    match a.checked_mul(shift) {
+        Some(shifted) => shifted.checked_add(b),
+        None => None,
+    }
+}
+
+/// Inverse of [`concat`]: the leading digits of `n` if it ends with the digits of `suffix`,
+/// e.g. `strip_suffix(12345, 45) == Some(123)`. `None` if `n` has no digits left in front of the suffix.
+pub const fn strip_suffix(n: u64, suffix: u64) -> Option<u64> {
+    let digits = digit_count(suffix);
+    if digit_count(n) <= digits {
+        return None;
+    }
+
+    let (prefix, rest) = split_at_digit(n, digits);
+    if rest == suffix {
+        Some(prefix)
+    } else {
+        None
+    }
+}
+
+/// Reverses the digits of `n`, e.g. `reverse_digits(1200) == Some(21)`. `None` on overflow.
+pub const fn reverse_digits(mut n: u64) -> Option<u64> {
+    let mut reversed: u64 = 0;
+
+    while n > 0 {
+        reversed = match reversed.checked_mul(10) {
+            Some(shifted) => match shifted.checked_add(n % 10) {
+                Some(value) => value,
+                None => return None,
+            },
+            None => return None,
+        };
+        n /= 10;
+    }
+
+    Some(reversed)
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn counts_digits() {
+        assert_eq!(digit_count(0), 1);
+        assert_eq!(digit_count(9), 1);
+        assert_eq!(digit_count(10), 2);
+        assert_eq!(digit_count(u64::MAX), 20);
+    }
+
+    #[test]
+    fn splits_numbers() {
+        assert_eq!(split_at_digit(12345, 2), (123, 45));
+        assert_eq!(split_at_digit(12345, 0), (12345, 0));
+        assert_eq!(split_at_digit(12345, 20), (0, 12345));
+        assert_eq!(split_in_half(1000), Some((10, 0)));
+        assert_eq!(split_in_half(253000), Some((253, 0)));
+        assert_eq!(split_in_half(125), None);
+    }
+
+    #[test]
+    fn concatenates_numbers() {
+        assert_eq!(concat(12, 345), Some(12345));
+        assert_eq!(concat(1, 0), Some(10));
+        assert_eq!(concat(u64::MAX, 1), None);
+        assert_eq!(concat(1, u64::MAX), None);
+        assert_eq!(strip_suffix(12345, 45), Some(123));
+        assert_eq!(strip_suffix(12345, 44), None);
+        assert_eq!(strip_suffix(45, 45), None);
+        assert_eq!(strip_suffix(100, 0), Some(10));
+    }
+
+    #[test]
+    fn reverses_digits() {
+        assert_eq!(reverse_digits(1234), Some(4321));
+        assert_eq!(reverse_digits(1200), Some(21));
+        assert_eq!(reverse_digits(0), Some(0));
+        assert_eq!(reverse_digits(u64::MAX), None);
+    }
+}
@@ -0,0 +1,196 @@
+//! Dynamic programming over grids whose cells form a directed acyclic graph, e.g. a height map where
+//! every step has to go up.
+use grid_2d::{Coord, Grid, Size};
+
+fn neighbours(position: Coord) -> [Coord; 4] {
+    [
+        position + Coord::new(-1, 0),
+        position + Coord::new(1, 0),
+        position + Coord::new(0, -1),
+        position + Coord::new(0, 1),
+    ]
+}
+
+/// Row-major index of a coord inside a grid of `size`, like `Grid::index_of_coord_unchecked`.
+fn index_of_coord_unchecked(size: Size, coord: Coord) -> usize {
+    coord.y as usize * size.width() as usize + coord.x as usize
+}
+
+/// The cells of a grid with edges between orthogonally adjacent cells.
+pub struct GridDag {
+    size: Size,
+    successors: Vec<Vec<usize>>,
+    /// Cell indices in topological order, every cell comes before its successors.
+    order: Vec<usize>,
+}
+
+impl GridDag {
+    /// Adds an edge from every cell to each adjacent cell where `edge(from, to)` holds.
+    /// Returns `None` if the edges contain a cycle.
+    pub fn new<T>(grid: &Grid<T>, edge: impl Fn(&T, &T) -> bool) -> Option<Self> {
+        let size = grid.size();
+        let mut successors = vec![vec![]; grid.len()];
+        let mut in_degree = vec![0; grid.len()];
+
+        for (from, value) in grid.enumerate() {
+            for to in neighbours(from) {
+                if grid.get(to).is_some_and(|next| edge(value, next)) {
+                    let to = grid.index_of_coord_unchecked(to);
+                    successors[grid.index_of_coord_unchecked(from)].push(to);
+                    in_degree[to] += 1;
+                }
+            }
+        }
+
+        // Kahn's algorithm
+        let mut order: Vec<usize> = (0..grid.len()).filter(|i| in_degree[*i] == 0).collect();
+        let mut next = 0;
+        while let Some(&index) = order.get(next) {
+            for &successor in &successors[index] {
+                in_degree[successor] -= 1;
+                if in_degree[successor] == 0 {
+                    order.push(successor);
+                }
+            }
+            next += 1;
+        }
+
+        (order.len() == grid.len()).then_some(Self {
+            size,
+            successors,
+            order,
+        })
+    }
+
+    fn coord(&self, index: usize) -> Coord {
+        let width = self.size.width() as usize;
+        Coord::new((index % width) as i32, (index / width) as i32)
+    }
+
+    /// Number of paths from every cell that end in a target. A target counts as a path of its own,
+    /// paths may continue through targets to further targets.
+    pub fn path_counts(&self, is_target: impl Fn(Coord) -> bool) -> Grid<u64> {
+        let mut counts = vec![0u64; self.order.len()];
+
+        for &index in self.order.iter().rev() {
+            let through: u64 = self.successors[index].iter().map(|s| counts[*s]).sum();
+            counts[index] = through + u64::from(is_target(self.coord(index)));
+        }
+
+        Grid::new_iterator(self.size, counts.into_iter())
+    }
+
+    /// The targets reachable from every cell, a target reaches itself.
+    pub fn reachable_targets(&self, is_target: impl Fn(Coord) -> bool) -> ReachableTargets {
+        let targets: Vec<Coord> = (0..self.order.len())
+            .map(|index| self.coord(index))
+            .filter(|coord| is_target(*coord))
+            .collect();
+        let words = targets.len().div_ceil(64);
+        let mut sets = vec![vec![0u64; words]; self.order.len()];
+
+        for (bit, target) in targets.iter().enumerate() {
+            sets[index_of_coord_unchecked(self.size, *target)][bit / 64] |= 1 << (bit % 64);
+        }
+
+        for &index in self.order.iter().rev() {
+            for &successor in &self.successors[index] {
+                let (reached, own) = if successor < index {
+                    let (left, right) = sets.split_at_mut(index);
+                    (&left[successor], &mut right[0])
+                } else {
+                    let (left, right) = sets.split_at_mut(successor);
+                    (&right[0], &mut left[index])
+                };
+                for (word, reached) in own.iter_mut().zip(reached) {
+                    *word |= reached;
+                }
+            }
+        }
+
+        ReachableTargets {
+            size: self.size,
+            targets,
+            sets,
+        }
+    }
+}
+
+/// Sets of targets per cell, see [`GridDag::reachable_targets`].
+pub struct ReachableTargets {
+    size: Size,
+    targets: Vec<Coord>,
+    sets: Vec<Vec<u64>>,
+}
+
+impl ReachableTargets {
+    fn set(&self, coord: Coord) -> Option<&[u64]> {
+        coord
+            .is_valid(self.size)
+            .then(|| self.sets[index_of_coord_unchecked(self.size, coord)].as_slice())
+    }
+
+    /// Number of targets reachable from `coord`, zero if it is outside the grid.
+    pub fn count(&self, coord: Coord) -> usize {
+        self.set(coord).map_or(0, |set| {
+            set.iter().map(|word| word.count_ones() as usize).sum()
+        })
+    }
+
+    /// The targets reachable from `coord`, in reading order. Empty if it is outside the grid.
+    pub fn iter(&self, coord: Coord) -> impl Iterator<Item = Coord> + '_ {
+        let set = self.set(coord);
+
+        self.targets
+            .iter()
+            .enumerate()
+            .filter(move |(bit, _)| set.is_some_and(|set| set[bit / 64] & (1 << (bit % 64)) != 0))
+            .map(|(_, target)| *target)
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::*;
+
+    fn grid(rows: &[&str]) -> Grid<u8> {
+        let size = Size::new(rows[0].len() as u32, rows.len() as u32);
+        Grid::new_iterator(size, rows.iter().flat_map(|row| row.bytes()))
+    }
+
+    #[test]
+    fn counts_paths() {
+        // two ways around the middle from the top left to the bottom right.
+        let grid = grid(&["012", "123", "234"]);
+        let dag = GridDag::new(&grid, |from, to| *to == from + 1).unwrap();
+        let counts = dag.path_counts(|coord| coord == Coord::new(2, 2));
+
+        assert_eq!(*counts.get_checked(Coord::new(0, 0)), 6);
+        assert_eq!(*counts.get_checked(Coord::new(1, 1)), 2);
+        assert_eq!(*counts.get_checked(Coord::new(2, 2)), 1);
+        assert_eq!(*counts.get_checked(Coord::new(2, 0)), 1);
+    }
+
+    #[test]
+    fn finds_reachable_targets() {
+        let grid = grid(&["3210", "4501", "5612"]);
+        let dag = GridDag::new(&grid, |from, to| *to == from + 1).unwrap();
+        let reachable = dag.reachable_targets(|coord| *grid.get_checked(coord) == b'2');
+
+        assert_eq!(reachable.count(Coord::new(3, 0)), 2);
+        assert_eq!(
+            reachable.iter(Coord::new(3, 0)).collect::<Vec<_>>(),
+            [Coord::new(1, 0), Coord::new(3, 2)]
+        );
+        assert_eq!(reachable.count(Coord::new(0, 0)), 0);
+        assert_eq!(reachable.count(Coord::new(9, 9)), 0);
+        assert_eq!(reachable.iter(Coord::new(9, 9)).next(), None);
+    }
+
+    #[test]
+    fn detects_cycles() {
+        let grid = grid(&["00", "00"]);
+        assert_eq!(GridDag::new(&grid, |from, to| from == to).is_none(), true);
+        assert_eq!(GridDag::new(&grid, |_, _| false).is_some(), true);
+    }
+}
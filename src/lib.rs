@@ -1,3 +1,7 @@
+pub mod digits;
+pub mod grid_dag;
+pub mod math;
+pub mod regions;
 pub mod template;
 
 // Use this file to add helper functions and additional modules.
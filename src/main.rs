@@ -1,4 +1,7 @@
-use advent_of_code::template::commands::{all, download, read, scaffold, solve, time};
+use advent_of_code::template::commands::{
+    all, download, examples, read, readme, scaffold, solve, time,
+};
+use advent_of_code::template::DaySet;
 use args::{parse, AppArguments};
 
 #[cfg(feature = "today")]
@@ -7,72 +10,154 @@ use advent_of_code::template::Day;
 use std::process;
 
 mod args {
-    use advent_of_code::template::Day;
+    use advent_of_code::template::{benchmark_report::ReportFormat, Day, DayFilters, DaySet};
     use std::process;
 
     pub enum AppArguments {
         Download {
+            days: Option<DaySet>,
+            filters: DayFilters,
+        },
+        Examples {
             day: Day,
+            write: Option<Vec<usize>>,
+            overwrite: bool,
+            update_tests: bool,
         },
         Read {
             day: Day,
+            part: Option<u8>,
         },
+        Readme,
         Scaffold {
-            day: Day,
+            days: DaySet,
             download: bool,
             overwrite: bool,
+            dry_run: bool,
         },
         Solve {
             day: Day,
             release: bool,
             dhat: bool,
+            profile_cpu: bool,
             submit: Option<u8>,
+            params: Vec<String>,
         },
         All {
+            days: Option<DaySet>,
+            filters: DayFilters,
             release: bool,
         },
         Time {
             all: bool,
-            day: Option<Day>,
+            days: Option<DaySet>,
+            filters: DayFilters,
             store: bool,
+            report: Option<ReportFormat>,
+            out: Option<String>,
+            variants: bool,
         },
         #[cfg(feature = "today")]
         Today,
     }
 
+    fn parse_filters(
+        args: &mut pico_args::Arguments,
+    ) -> Result<DayFilters, Box<dyn std::error::Error>> {
+        Ok(DayFilters {
+            unsolved: args.contains("--unsolved"),
+            changed: args.contains("--changed"),
+            slowest: args.opt_value_from_str("--slowest")?,
+        })
+    }
+
+    fn parse_block_list(s: &str) -> Result<Vec<usize>, std::num::ParseIntError> {
+        s.split(',').map(|block| block.trim().parse()).collect()
+    }
+
     pub fn parse() -> Result<AppArguments, Box<dyn std::error::Error>> {
         let mut args = pico_args::Arguments::from_env();
 
         let app_args = match args.subcommand()?.as_deref() {
-            Some("all") => AppArguments::All {
-                release: args.contains("--release"),
-            },
+            Some("all") => {
+                let release = args.contains("--release");
+                let filters = parse_filters(&mut args)?;
+
+                AppArguments::All {
+                    days: args.opt_free_from_str()?,
+                    filters,
+                    release,
+                }
+            }
             Some("time") => {
                 let all = args.contains("--all");
                 let store = args.contains("--store");
+                let report = args.opt_value_from_str("--report")?;
+                let out = args.opt_value_from_str("--out")?;
+                let variants = args.contains("--variants");
+                let filters = parse_filters(&mut args)?;
 
                 AppArguments::Time {
                     all,
-                    day: args.opt_free_from_str()?,
+                    days: args.opt_free_from_str()?,
+                    filters,
                     store,
+                    report,
+                    out,
+                    variants,
+                }
+            }
+            Some("download") => {
+                let filters = parse_filters(&mut args)?;
+                let days: Option<DaySet> = args.opt_free_from_str()?;
+
+                if days.is_none() && !filters.unsolved && !filters.changed {
+                    eprintln!("Specify the days to download, e.g. `cargo download 1-5,8`.");
+                    process::exit(1);
+                }
+
+                AppArguments::Download { days, filters }
+            }
+            Some("examples") => {
+                let write = args.opt_value_from_fn("--write", parse_block_list)?;
+                let overwrite = args.contains("--overwrite");
+                let update_tests = args.contains("--update-tests");
+
+                AppArguments::Examples {
+                    day: args.free_from_str()?,
+                    write,
+                    overwrite,
+                    update_tests,
+                }
+            }
+            Some("read") => {
+                let part = args.opt_value_from_str("--part")?;
+
+                AppArguments::Read {
+                    day: args.free_from_str()?,
+                    part,
+                }
+            }
+            Some("readme") => AppArguments::Readme,
+            Some("scaffold") => {
+                let download = args.contains("--download");
+                let overwrite = args.contains("--overwrite");
+                let dry_run = args.contains("--dry-run");
+
+                AppArguments::Scaffold {
+                    days: args.free_from_str()?,
+                    download,
+                    overwrite,
+                    dry_run,
                 }
             }
-            Some("download") => AppArguments::Download {
-                day: args.free_from_str()?,
-            },
-            Some("read") => AppArguments::Read {
-                day: args.free_from_str()?,
-            },
-            Some("scaffold") => AppArguments::Scaffold {
-                day: args.free_from_str()?,
-                download: args.contains("--download"),
-                overwrite: args.contains("--overwrite"),
-            },
             Some("solve") => AppArguments::Solve {
                 day: args.free_from_str()?,
                 release: args.contains("--release"),
                 submit: args.opt_value_from_str("--submit")?,
                 dhat: args.contains("--dhat"),
+                profile_cpu: args.contains("--profile-cpu"),
+                params: args.values_from_str("--param")?,
             },
             #[cfg(feature = "today")]
             Some("today") => AppArguments::Today,
@@ -102,38 +187,63 @@ fn main() {
             std::process::exit(1);
         }
         Ok(args) => match args {
-            AppArguments::All { release } => all::handle(release),
-            AppArguments::Time { day, all, store } => time::handle(day, all, store),
-            AppArguments::Download { day } => download::handle(day),
-            AppArguments::Read { day } => read::handle(day),
-            AppArguments::Scaffold {
+            AppArguments::All {
+                days,
+                filters,
+                release,
+            } => all::handle(days, filters, release),
+            AppArguments::Time {
+                days,
+                filters,
+                all,
+                store,
+                report,
+                out,
+                variants,
+            } => time::handle(days, filters, all, store, report, out, variants),
+            AppArguments::Download { days, filters } => {
+                download::handle_set(&filters.apply(days.unwrap_or_else(DaySet::all)));
+            }
+            AppArguments::Examples {
                 day,
+                write,
+                overwrite,
+                update_tests,
+            } => examples::handle(day, write, overwrite, update_tests),
+            AppArguments::Read { day, part } => read::handle(day, part),
+            AppArguments::Readme => readme::handle(),
+            AppArguments::Scaffold {
+                days,
                 download,
                 overwrite,
+                dry_run,
             } => {
-                scaffold::handle(day, overwrite);
-                if download {
-                    download::handle(day);
+                scaffold::handle_set(&days, overwrite, dry_run);
+                if download && !dry_run {
+                    download::handle_set(&days);
                 }
             }
             AppArguments::Solve {
                 day,
                 release,
                 dhat,
+                profile_cpu,
                 submit,
-            } => solve::handle(day, release, dhat, submit),
+                params,
+            } => solve::handle(day, release, dhat, profile_cpu, submit, &params),
             #[cfg(feature = "today")]
             AppArguments::Today => {
                 match Day::today() {
                     Some(day) => {
-                        scaffold::handle(day, false);
+                        scaffold::handle(day, false, false);
                         download::handle(day);
-                        read::handle(day)
+                        read::handle(day, None)
                     }
                     None => {
                         eprintln!(
                             "`today` command can only be run between the 1st and \
-                            the 25th of december. Please use `scaffold` with a specific day."
+                            the {} of december. Please use `scaffold` with a specific day.",
+                            ordinal(Day::days_in_current_event())
                         );
                         process::exit(1)
                     }
@@ -142,3 +252,15 @@ fn main() {
         },
     };
 }
+
+#[cfg(feature = "today")]
+fn ordinal(n: u8) -> String {
+    let suffix = match (n % 10, n % 100) {
+        (1, 11) | (2, 12) | (3, 13) => "th",
+        (1, _) => "st",
+        (2, _) => "nd",
+        (3, _) => "rd",
+        _ => "th",
+    };
+    format!("{n}{suffix}")
+}
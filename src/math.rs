@@ -0,0 +1,486 @@
+//! Integer number theory and exact linear algebra for puzzles.
+//!
+//! Every function has a `checked_` variant that reports overflow and division by zero as a [`MathError`],
+//! the plain variants panic in these cases. Intermediate results are computed with `i128`.
+use std::cmp::Ordering;
+use std::fmt::Display;
+
+/// An error reported by the checked functions of this module.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MathError {
+    Overflow,
+    DivisionByZero,
+}
+
+impl std::error::Error for MathError {}
+
+impl Display for MathError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MathError::Overflow => f.write_str("arithmetic overflow"),
+            MathError::DivisionByZero => f.write_str("division by zero"),
+        }
+    }
+}
+
+fn narrow(value: i128) -> Result<i64, MathError> {
+    i64::try_from(value).map_err(|_| MathError::Overflow)
+}
+
+fn expect<T>(result: Result<T, MathError>) -> T {
+    result.unwrap_or_else(|e| panic!("{e}"))
+}
+
+/* -------------------------------------------------------------------------- */
+
+fn ext_gcd_wide(a: i128, b: i128) -> (i128, i128, i128) {
+    let (mut old_r, mut r) = (a, b);
+    let (mut old_s, mut s) = (1, 0);
+    let (mut old_t, mut t) = (0, 1);
+
+    while r != 0 {
+        let q = old_r / r;
+        (old_r, r) = (r, old_r - q * r);
+        (old_s, s) = (s, old_s - q * s);
+        (old_t, t) = (t, old_t - q * t);
+    }
+
+    if old_r < 0 {
+        (-old_r, -old_s, -old_t)
+    } else {
+        (old_r, old_s, old_t)
+    }
+}
+
+/// Greatest common divisor, always non-negative.
+pub fn gcd(a: i64, b: i64) -> i64 {
+    expect(checked_gcd(a, b))
+}
+
+/// Greatest common divisor, fails if the result does not fit into `i64` (e.g. `gcd(i64::MIN, 0)`).
+pub fn checked_gcd(a: i64, b: i64) -> Result<i64, MathError> {
+    narrow(ext_gcd_wide(i128::from(a), i128::from(b)).0)
+}
+
+/// Least common multiple, always non-negative.
+pub fn lcm(a: i64, b: i64) -> i64 {
+    expect(checked_lcm(a, b))
+}
+
+pub fn checked_lcm(a: i64, b: i64) -> Result<i64, MathError> {
+    if a == 0 || b == 0 {
+        return Ok(0);
+    }
+    let (a, b) = (i128::from(a), i128::from(b));
+    narrow((a / ext_gcd_wide(a, b).0 * b).abs())
+}
+
+/// Extended Euclidean algorithm: returns `(g, x, y)` with `a * x + b * y = g = gcd(a, b)`.
+pub fn ext_gcd(a: i64, b: i64) -> (i64, i64, i64) {
+    expect(checked_ext_gcd(a, b))
+}
+
+pub fn checked_ext_gcd(a: i64, b: i64) -> Result<(i64, i64, i64), MathError> {
+    let (g, x, y) = ext_gcd_wide(i128::from(a), i128::from(b));
+    Ok((narrow(g)?, narrow(x)?, narrow(y)?))
+}
+
+/// Inverse of `a` modulo `m` in `0..m`, `None` if `a` and `m` are not coprime.
+pub fn mod_inv(a: i64, m: i64) -> Option<i64> {
+    expect(checked_mod_inv(a, m))
+}
+
+pub fn checked_mod_inv(a: i64, m: i64) -> Result<Option<i64>, MathError> {
+    if m == 0 {
+        return Err(MathError::DivisionByZero);
+    }
+    let m = i128::from(m).abs();
+    let (g, x, _) = ext_gcd_wide(i128::from(a).rem_euclid(m), m);
+
+    if g != 1 {
+        return Ok(None);
+    }
+    Ok(Some(narrow(x.rem_euclid(m))?))
+}
+
+/// Chinese remainder theorem for congruences `x ≡ residue (mod modulus)`, moduli do not need to be coprime.
+/// Returns the smallest non-negative solution and the combined modulus, `None` if the congruences contradict.
+pub fn crt(congruences: &[(i64, i64)]) -> Option<(i64, i64)> {
+    expect(checked_crt(congruences))
+}
+
+pub fn checked_crt(congruences: &[(i64, i64)]) -> Result<Option<(i64, i64)>, MathError> {
+    let mut residue: i128 = 0;
+    let mut modulus: i128 = 1;
+
+    for &(r, m) in congruences {
+        if m == 0 {
+            return Err(MathError::DivisionByZero);
+        }
+        let m = i128::from(m).abs();
+        let r = i128::from(r).rem_euclid(m);
+
+        // solve residue + modulus * k ≡ r (mod m)
+        let (g, x, _) = ext_gcd_wide(modulus, m);
+        if (r - residue) % g != 0 {
+            return Ok(None);
+        }
+
+        let step = m / g;
+        let k = ((r - residue) / g % step * (x % step)).rem_euclid(step);
+        residue = residue
+            .checked_add(modulus.checked_mul(k).ok_or(MathError::Overflow)?)
+            .ok_or(MathError::Overflow)?;
+        modulus = modulus.checked_mul(step).ok_or(MathError::Overflow)?;
+        narrow(modulus)?;
+        residue = residue.rem_euclid(modulus);
+    }
+
+    Ok(Some((narrow(residue)?, narrow(modulus)?)))
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// An exact fraction, always stored in lowest terms with a positive denominator.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Rational {
+    num: i128,
+    den: i128,
+}
+
+impl Rational {
+    pub const ZERO: Self = Self { num: 0, den: 1 };
+    pub const ONE: Self = Self { num: 1, den: 1 };
+
+    pub fn new(num: i64, den: i64) -> Self {
+        expect(Self::checked_new(i128::from(num), i128::from(den)))
+    }
+
+    fn checked_new(num: i128, den: i128) -> Result<Self, MathError> {
+        if den == 0 {
+            return Err(MathError::DivisionByZero);
+        }
+        let g = ext_gcd_wide(num, den).0;
+        let sign = if den < 0 { -1 } else { 1 };
+        Ok(Self {
+            num: sign * num / g,
+            den: sign * den / g,
+        })
+    }
+
+    pub fn numerator(self) -> i128 {
+        self.num
+    }
+
+    pub fn denominator(self) -> i128 {
+        self.den
+    }
+
+    pub fn is_integer(self) -> bool {
+        self.den == 1
+    }
+
+    /// The value as an integer, `None` if it is not integral or does not fit into `i64`.
+    pub fn to_integer(self) -> Option<i64> {
+        if self.is_integer() {
+            i64::try_from(self.num).ok()
+        } else {
+            None
+        }
+    }
+
+    pub fn checked_add(self, other: Self) -> Result<Self, MathError> {
+        let g = ext_gcd_wide(self.den, other.den).0;
+        let num = (self.num.checked_mul(other.den / g))
+            .zip(other.num.checked_mul(self.den / g))
+            .and_then(|(a, b)| a.checked_add(b))
+            .ok_or(MathError::Overflow)?;
+        let den = (self.den / g)
+            .checked_mul(other.den)
+            .ok_or(MathError::Overflow)?;
+        Self::checked_new(num, den)
+    }
+
+    pub fn checked_sub(self, other: Self) -> Result<Self, MathError> {
+        self.checked_add(Self {
+            num: -other.num,
+            den: other.den,
+        })
+    }
+
+    pub fn checked_mul(self, other: Self) -> Result<Self, MathError> {
+        // cross-reduce first to keep intermediate values small.
+        let g1 = ext_gcd_wide(self.num, other.den).0.max(1);
+        let g2 = ext_gcd_wide(other.num, self.den).0.max(1);
+        let num = (self.num / g1)
+            .checked_mul(other.num / g2)
+            .ok_or(MathError::Overflow)?;
+        let den = (self.den / g2)
+            .checked_mul(other.den / g1)
+            .ok_or(MathError::Overflow)?;
+        Self::checked_new(num, den)
+    }
+
+    pub fn checked_div(self, other: Self) -> Result<Self, MathError> {
+        if other.num == 0 {
+            return Err(MathError::DivisionByZero);
+        }
+        self.checked_mul(Self {
+            num: other.den,
+            den: other.num,
+        })
+    }
+}
+
+impl From<i64> for Rational {
+    fn from(value: i64) -> Self {
+        Self {
+            num: i128::from(value),
+            den: 1,
+        }
+    }
+}
+
+/// Compares `a_num / a_den` with `b_num / b_den` for positive denominators without overflowing,
+/// by comparing the integer parts and then the reciprocals of the remainders, like a continued fraction.
+fn cmp_fractions(a_num: i128, a_den: i128, b_num: i128, b_den: i128) -> Ordering {
+    let (a_int, a_rem) = (a_num.div_euclid(a_den), a_num.rem_euclid(a_den));
+    let (b_int, b_rem) = (b_num.div_euclid(b_den), b_num.rem_euclid(b_den));
+
+    match (a_int.cmp(&b_int), a_rem, b_rem) {
+        (Ordering::Equal, 0, 0) => Ordering::Equal,
+        (Ordering::Equal, 0, _) => Ordering::Less,
+        (Ordering::Equal, _, 0) => Ordering::Greater,
+        // a larger remainder means a smaller reciprocal.
+        (Ordering::Equal, _, _) => cmp_fractions(b_den, b_rem, a_den, a_rem),
+        (ordering, _, _) => ordering,
+    }
+}
+
+impl Ord for Rational {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // denominators are positive, compare the cross products if they fit.
+        match (
+            self.num.checked_mul(other.den),
+            other.num.checked_mul(self.den),
+        ) {
+            (Some(left), Some(right)) => left.cmp(&right),
+            _ => cmp_fractions(self.num, self.den, other.num, other.den),
+        }
+    }
+}
+
+impl PartialOrd for Rational {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Display for Rational {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.is_integer() {
+            write!(f, "{}", self.num)
+        } else {
+            write!(f, "{}/{}", self.num, self.den)
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// The solutions of a linear system `A x = b`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LinearSolution {
+    Unique(Vec<Rational>),
+    /// The system is singular but consistent, i.e. has infinitely many rational solutions.
+    Infinite,
+    None,
+}
+
+impl LinearSolution {
+    /// The unique solution if all of its components are integers.
+    pub fn integer_solution(&self) -> Option<Vec<i64>> {
+        match self {
+            LinearSolution::Unique(values) => values.iter().map(|v| v.to_integer()).collect(),
+            _ => None,
+        }
+    }
+}
+
+/// Solves the square system `matrix * x = rhs` exactly with Gaussian elimination over rationals.
+pub fn solve_linear(matrix: &[Vec<i64>], rhs: &[i64]) -> LinearSolution {
+    expect(checked_solve_linear(matrix, rhs))
+}
+
+/// # Panics
+/// Panics if the matrix is not square or does not match the length of `rhs`.
+pub fn checked_solve_linear(matrix: &[Vec<i64>], rhs: &[i64]) -> Result<LinearSolution, MathError> {
+    let n = rhs.len();
+    assert!(
+        matrix.len() == n && matrix.iter().all(|row| row.len() == n),
+        "expecting a square matrix matching the right-hand side"
+    );
+
+    // augmented matrix [A | b]
+    let mut rows: Vec<Vec<Rational>> = matrix
+        .iter()
+        .zip(rhs)
+        .map(|(row, b)| row.iter().chain([b]).map(|v| Rational::from(*v)).collect())
+        .collect();
+
+    let mut rank = 0;
+    for col in 0..n {
+        let Some(pivot) = (rank..n).find(|&r| rows[r][col] != Rational::ZERO) else {
+            continue;
+        };
+        rows.swap(rank, pivot);
+        let pivot_row = rows[rank].clone();
+
+        for (r, row) in rows.iter_mut().enumerate() {
+            if r == rank || row[col] == Rational::ZERO {
+                continue;
+            }
+            let factor = row[col].checked_div(pivot_row[col])?;
+            for (value, pivot_value) in row.iter_mut().zip(&pivot_row).skip(col) {
+                *value = value.checked_sub(factor.checked_mul(*pivot_value)?)?;
+            }
+        }
+        rank += 1;
+    }
+
+    // a zero row with a non-zero right-hand side is a contradiction.
+    if rows[rank..].iter().any(|row| row[n] != Rational::ZERO) {
+        return Ok(LinearSolution::None);
+    }
+    if rank < n {
+        return Ok(LinearSolution::Infinite);
+    }
+
+    let values = rows
+        .iter()
+        .enumerate()
+        .map(|(i, row)| row[n].checked_div(row[i]))
+        .collect::<Result<_, _>>()?;
+
+    Ok(LinearSolution::Unique(values))
+}
+
+/// Solves the 2x2 system `[[a, b], [c, d]] * [x, y] = [e, f]`, see [`solve_linear`].
+pub fn solve_2x2(matrix: [[i64; 2]; 2], rhs: [i64; 2]) -> LinearSolution {
+    expect(checked_solve_2x2(matrix, rhs))
+}
+
+pub fn checked_solve_2x2(
+    matrix: [[i64; 2]; 2],
+    rhs: [i64; 2],
+) -> Result<LinearSolution, MathError> {
+    checked_solve_linear(&[matrix[0].to_vec(), matrix[1].to_vec()], &rhs)
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn computes_gcd_and_lcm() {
+        assert_eq!(gcd(12, -18), 6);
+        assert_eq!(gcd(0, 0), 0);
+        assert_eq!(lcm(4, -6), 12);
+        assert_eq!(lcm(0, 5), 0);
+        assert_eq!(checked_gcd(i64::MIN, 0), Err(MathError::Overflow));
+        assert_eq!(
+            checked_lcm(i64::MAX, i64::MAX - 1),
+            Err(MathError::Overflow)
+        );
+    }
+
+    #[test]
+    fn computes_bezout_coefficients() {
+        for (a, b) in [(240, 46), (-7, 3), (0, 5), (17, 0), (12, 18)] {
+            let (g, x, y) = ext_gcd(a, b);
+            assert_eq!(g, gcd(a, b));
+            assert_eq!(a * x + b * y, g);
+        }
+    }
+
+    #[test]
+    fn computes_modular_inverses() {
+        assert_eq!(mod_inv(3, 11), Some(4));
+        assert_eq!(mod_inv(-3, 11), Some(7));
+        assert_eq!(mod_inv(6, 9), None);
+        assert_eq!(checked_mod_inv(3, 0), Err(MathError::DivisionByZero));
+    }
+
+    #[test]
+    fn solves_congruences() {
+        assert_eq!(crt(&[(2, 3), (3, 5), (2, 7)]), Some((23, 105)));
+        // non-coprime moduli
+        assert_eq!(crt(&[(2, 4), (4, 6)]), Some((10, 12)));
+        assert_eq!(crt(&[(1, 4), (2, 6)]), None);
+        assert_eq!(crt(&[]), Some((0, 1)));
+        assert_eq!(
+            checked_crt(&[(0, i64::MAX), (1, i64::MAX - 1)]),
+            Err(MathError::Overflow)
+        );
+    }
+
+    #[test]
+    fn computes_with_rationals() {
+        let half = Rational::new(2, 4);
+        assert_eq!(half, Rational::new(-1, -2));
+        assert_eq!(half.to_string(), "1/2");
+        let sum = half.checked_add(Rational::new(1, 3)).unwrap();
+        assert_eq!(sum, Rational::new(5, 6));
+        assert_eq!(sum.checked_mul(Rational::new(6, 5)).unwrap(), Rational::ONE);
+        assert_eq!(
+            half.checked_div(Rational::ZERO),
+            Err(MathError::DivisionByZero)
+        );
+        assert_eq!(Rational::new(1, 3) < half, true);
+    }
+
+    #[test]
+    fn compares_large_rationals() {
+        let rational = |num, den| Rational { num, den };
+        let max = i128::MAX;
+        // 1 + 1 / (max - 1) and 1 + 1 / (max - 2), the cross products overflow.
+        let (a, b) = (rational(max, max - 1), rational(max - 1, max - 2));
+        assert_eq!(a < b, true);
+        assert_eq!(b.cmp(&a), Ordering::Greater);
+        assert_eq!(a.cmp(&a), Ordering::Equal);
+        assert_eq!(rational(-max, max - 1) < rational(1, max), true);
+        assert_eq!(rational(max, 2) > rational(max - 1, 2), true);
+    }
+
+    #[test]
+    fn solves_2x2_systems() {
+        let solution = solve_2x2([[94, 22], [34, 67]], [8400, 5400]);
+        assert_eq!(solution.integer_solution(), Some(vec![80, 40]));
+
+        let solution = solve_2x2([[26, 67], [66, 21]], [12748, 12176]);
+        assert_eq!(solution.integer_solution(), None);
+        assert_eq!(matches!(solution, LinearSolution::Unique(_)), true);
+
+        assert_eq!(
+            solve_2x2([[1, 2], [2, 4]], [3, 6]),
+            LinearSolution::Infinite
+        );
+        assert_eq!(solve_2x2([[1, 2], [2, 4]], [3, 7]), LinearSolution::None);
+    }
+
+    #[test]
+    fn solves_nxn_systems() {
+        let solution = solve_linear(&[vec![94, 22], vec![34, 67]], &[8400, 5400]);
+        assert_eq!(solution.integer_solution(), Some(vec![80, 40]));
+
+        let matrix = [vec![2, 1, -1], vec![-3, -1, 2], vec![-2, 1, 2]];
+        let solution = solve_linear(&matrix, &[8, -11, -3]);
+        assert_eq!(solution.integer_solution(), Some(vec![2, 3, -1]));
+
+        let solution = solve_linear(&[vec![2, 0], vec![0, 3]], &[1, 1]);
+        assert_eq!(
+            solution,
+            LinearSolution::Unique(vec![Rational::new(1, 2), Rational::new(1, 3)])
+        );
+    }
+}
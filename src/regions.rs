@@ -0,0 +1,266 @@
+//! Connected regions of equal cells in a grid and their geometry: area, perimeter, sides, bounding box
+//! and, on demand, holes.
+use std::collections::VecDeque;
+
+use grid_2d::{Coord, Grid, Size};
+
+const ORTHOGONAL: [Coord; 4] = [
+    Coord { x: 0, y: -1 },
+    Coord { x: 1, y: 0 },
+    Coord { x: 0, y: 1 },
+    Coord { x: -1, y: 0 },
+];
+
+const DIAGONAL: [Coord; 4] = [
+    Coord { x: -1, y: -1 },
+    Coord { x: 1, y: -1 },
+    Coord { x: 1, y: 1 },
+    Coord { x: -1, y: 1 },
+];
+
+/// A region of orthogonally connected cells with equal values.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Region {
+    pub label: usize,
+    /// The first cell of the region in reading order.
+    pub start: Coord,
+    pub area: usize,
+    pub perimeter: usize,
+    /// Number of straight fence sections, equal to the number of corners.
+    pub sides: usize,
+    /// Top left corner of the bounding box.
+    pub min: Coord,
+    /// Bottom right corner of the bounding box, inclusive.
+    pub max: Coord,
+}
+
+/// The areas of other cells a region encloses, see [`Regions::holes`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Holes {
+    /// Number of enclosed areas. Cells touching diagonally belong to the same hole.
+    pub count: usize,
+    /// Labels of the regions lying inside the holes, sorted.
+    pub enclosed: Vec<usize>,
+}
+
+/// All regions of a grid, labelled in reading order of their first cell.
+pub struct Regions {
+    labels: Grid<usize>,
+    regions: Vec<Region>,
+}
+
+impl Regions {
+    pub fn new<T: PartialEq>(grid: &Grid<T>) -> Self {
+        let labels = label_regions(grid);
+        let count = labels.iter().max().map_or(0, |max| max + 1);
+
+        let mut regions: Vec<Region> = (0..count)
+            .map(|label| Region {
+                label,
+                start: Coord::new(i32::MAX, i32::MAX),
+                area: 0,
+                perimeter: 0,
+                sides: 0,
+                min: Coord::new(i32::MAX, i32::MAX),
+                max: Coord::new(i32::MIN, i32::MIN),
+            })
+            .collect();
+
+        for (coord, &label) in labels.enumerate() {
+            let same = |offset: Coord| labels.get(coord + offset) == Some(&label);
+            let region = &mut regions[label];
+
+            if region.area == 0 {
+                region.start = coord;
+            }
+            region.area += 1;
+            region.perimeter += ORTHOGONAL.iter().filter(|offset| !same(**offset)).count();
+            region.sides += DIAGONAL
+                .iter()
+                .filter(|diagonal| {
+                    let horizontal = same(Coord::new(diagonal.x, 0));
+                    let vertical = same(Coord::new(0, diagonal.y));
+                    // convex corner, or concave corner around a missing diagonal cell.
+                    (!horizontal && !vertical) || (horizontal && vertical && !same(**diagonal))
+                })
+                .count();
+            region.min = Coord::new(region.min.x.min(coord.x), region.min.y.min(coord.y));
+            region.max = Coord::new(region.max.x.max(coord.x), region.max.y.max(coord.y));
+        }
+
+        Self { labels, regions }
+    }
+
+    /// Label of the region containing `coord`, `None` if it is outside the grid.
+    pub fn label(&self, coord: Coord) -> Option<usize> {
+        self.labels.get(coord).copied()
+    }
+
+    pub fn get(&self, label: usize) -> Option<&Region> {
+        self.regions.get(label)
+    }
+
+    /// The holes of a region, computed on demand within its bounding box.
+    pub fn holes(&self, label: usize) -> Option<Holes> {
+        self.get(label)
+            .map(|region| find_holes(&self.labels, region))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Region> {
+        self.regions.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.regions.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.regions.is_empty()
+    }
+}
+
+fn label_regions<T: PartialEq>(grid: &Grid<T>) -> Grid<usize> {
+    let mut labels = Grid::new_copy(grid.size(), usize::MAX);
+    let mut next_label = 0;
+
+    for start in grid.coord_iter() {
+        if *labels.get_checked(start) != usize::MAX {
+            continue;
+        }
+
+        let value = grid.get_checked(start);
+        let mut queue = VecDeque::from([start]);
+        *labels.get_checked_mut(start) = next_label;
+
+        while let Some(coord) = queue.pop_front() {
+            for offset in ORTHOGONAL {
+                let next = coord + offset;
+                if grid.get(next) == Some(value) && labels.get(next) == Some(&usize::MAX) {
+                    *labels.get_checked_mut(next) = next_label;
+                    queue.push_back(next);
+                }
+            }
+        }
+
+        next_label += 1;
+    }
+
+    labels
+}
+
+/// Counts the areas of other cells the region encloses and collects the regions within them.
+/// The other cells are connected diagonally too, as they can pass between diagonal cells of the region.
+fn find_holes(labels: &Grid<usize>, region: &Region) -> Holes {
+    // the bounding box with a margin of one cell around it, which is always outside the region.
+    let origin = region.min - Coord::new(1, 1);
+    let size = Size::new(
+        (region.max.x - region.min.x + 3) as u32,
+        (region.max.y - region.min.y + 3) as u32,
+    );
+    let is_other = |local: Coord| labels.get(origin + local) != Some(&region.label);
+
+    let mut seen = Grid::new_copy(size, false);
+    let fill = |start: Coord, seen: &mut Grid<bool>| -> Vec<Coord> {
+        let mut cells = vec![start];
+        *seen.get_checked_mut(start) = true;
+        let mut next = 0;
+
+        while let Some(&local) = cells.get(next) {
+            for offset in ORTHOGONAL.iter().chain(&DIAGONAL) {
+                let neighbour = local + *offset;
+                if seen.get(neighbour) == Some(&false) && is_other(neighbour) {
+                    *seen.get_checked_mut(neighbour) = true;
+                    cells.push(neighbour);
+                }
+            }
+            next += 1;
+        }
+
+        cells
+    };
+
+    // everything connected to the margin is outside.
+    fill(Coord::new(0, 0), &mut seen);
+
+    let mut count = 0;
+    let mut enclosed = vec![];
+    for local in size.coord_iter_row_major() {
+        if *seen.get_checked(local) || !is_other(local) {
+            continue;
+        }
+
+        count += 1;
+        enclosed.extend(
+            fill(local, &mut seen)
+                .into_iter()
+                .map(|cell| *labels.get_checked(origin + cell)),
+        );
+    }
+    enclosed.sort_unstable();
+    enclosed.dedup();
+
+    Holes { count, enclosed }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::*;
+
+    fn regions(rows: &[&str]) -> Regions {
+        let size = Size::new(rows[0].len() as u32, rows.len() as u32);
+        Regions::new(&Grid::new_iterator(
+            size,
+            rows.iter().flat_map(|row| row.chars()),
+        ))
+    }
+
+    #[test]
+    fn measures_nested_regions() {
+        let regions = regions(&["OOOOO", "OXOXO", "OOOOO", "OXOXO", "OOOOO"]);
+        assert_eq!(regions.len(), 5);
+
+        let outer = regions.get(0).unwrap();
+        assert_eq!(outer.area, 21);
+        assert_eq!(outer.perimeter, 36);
+        assert_eq!(outer.sides, 20);
+        let holes = regions.holes(0).unwrap();
+        assert_eq!(holes.count, 4);
+        assert_eq!(holes.enclosed, [1, 2, 3, 4]);
+        assert_eq!((outer.min, outer.max), (Coord::new(0, 0), Coord::new(4, 4)));
+
+        let inner = regions
+            .get(regions.label(Coord::new(3, 3)).unwrap())
+            .unwrap();
+        assert_eq!((inner.area, inner.perimeter, inner.sides), (1, 4, 4));
+        assert_eq!(inner.start, Coord::new(3, 3));
+        assert_eq!(regions.holes(inner.label).unwrap().count, 0);
+        assert_eq!(regions.holes(5), None);
+    }
+
+    #[test]
+    fn counts_sides_of_concave_regions() {
+        let regions = regions(&["EEEEE", "EXXXX", "EEEEE", "EXXXX", "EEEEE"]);
+        let e = regions.get(0).unwrap();
+        assert_eq!((e.area, e.sides), (17, 12));
+        assert_eq!(regions.holes(0).unwrap().count, 0);
+        assert_eq!(
+            regions
+                .iter()
+                .map(|region| region.sides)
+                .collect::<Vec<_>>(),
+            [12, 4, 4]
+        );
+    }
+
+    #[test]
+    fn joins_diagonal_holes() {
+        let regions = regions(&["AAAAAA", "AAABBA", "AAABBA", "ABBAAA", "ABBAAA", "AAAAAA"]);
+        let a = regions.get(0).unwrap();
+        assert_eq!((a.area, a.sides), (28, 12));
+        // both B regions touch diagonally and form a single hole.
+        let holes = regions.holes(0).unwrap();
+        assert_eq!(holes.count, 1);
+        assert_eq!(holes.enclosed, [1, 2]);
+        assert_eq!(regions.holes(1).unwrap().enclosed.is_empty(), true);
+    }
+}
@@ -4,6 +4,7 @@ use std::{
     process::{Command, Output, Stdio},
 };
 
+use crate::template::submission::Submission;
 use crate::template::Day;
 
 #[derive(Debug)]
@@ -72,12 +73,42 @@ pub fn download(day: Day) -> Result<Output, AocCommandError> {
     Ok(output)
 }
 
-pub fn submit(day: Day, part: u8, result: &str) -> Result<Output, AocCommandError> {
+/// Downloads the puzzle description only, e.g. to pick up part two once part one is solved.
+pub fn download_puzzle(day: Day) -> Result<Output, AocCommandError> {
+    let puzzle_path = get_puzzle_path(day);
+
+    let args = build_args(
+        "download",
+        &[
+            "--overwrite".into(),
+            "--puzzle-only".into(),
+            "--puzzle-file".into(),
+            puzzle_path.to_string(),
+        ],
+        day,
+    );
+
+    let output = call_aoc_cli(&args)?;
+    println!("---");
+    println!("🎄 Successfully wrote puzzle to \"{}\".", &puzzle_path);
+    Ok(output)
+}
+
+/// Submits an answer and classifies the response.
+pub fn submit(day: Day, part: u8, result: &str) -> Result<Submission, AocCommandError> {
     // workaround: the argument order is inverted for submit.
     let mut args = build_args("submit", &[], day);
     args.push(part.to_string());
     args.push(result.to_string());
-    call_aoc_cli(&args)
+
+    let (output, text) = call_aoc_cli_captured(&args)?;
+    let submission = Submission::classify(&text);
+
+    if !output.status.success() && submission == Submission::Unknown {
+        return Err(AocCommandError::BadExitStatus(output));
+    }
+
+    Ok(submission)
 }
 
 fn get_input_path(day: Day) -> String {
@@ -88,7 +119,8 @@ fn get_puzzle_path(day: Day) -> String {
     format!("data/puzzles/{day}.md")
 }
 
-fn get_year() -> Option<u16> {
+/// Reads the configured event year from the `AOC_YEAR` environment variable.
+pub fn get_year() -> Option<u16> {
     match std::env::var("AOC_YEAR") {
         Ok(x) => x.parse().ok().or(None),
         Err(_) => None,
@@ -123,3 +155,20 @@ fn call_aoc_cli(args: &[String]) -> Result<Output, AocCommandError> {
         Err(AocCommandError::BadExitStatus(output))
     }
 }
+
+/// Calls aoc-cli with captured output, which is echoed afterwards and returned as text.
+fn call_aoc_cli_captured(args: &[String]) -> Result<(Output, String), AocCommandError> {
+    let output = Command::new("aoc")
+        .args(args)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .output()
+        .map_err(|_| AocCommandError::CommandNotCallable)?;
+
+    let stdout = String::from_utf8_lossy(&output.stdout).to_string();
+    let stderr = String::from_utf8_lossy(&output.stderr).to_string();
+    print!("{stdout}");
+    eprint!("{stderr}");
+
+    Ok((output, format!("{stdout}\n{stderr}")))
+}
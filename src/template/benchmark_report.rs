@@ -0,0 +1,348 @@
+/// Module that renders benchmark timings into standalone report files.
+use std::{collections::HashMap, fmt::Display, fs, io, str::FromStr};
+
+use tinyjson::JsonValue;
+
+use crate::template::run_multi::child_commands::parse_duration;
+use crate::template::timings::{Timing, Timings};
+
+const BAR_HEIGHT: usize = 14;
+const ROW_HEIGHT: usize = 3 * BAR_HEIGHT + 12;
+const LABEL_WIDTH: usize = 70;
+const CHART_WIDTH: usize = 560;
+const VALUE_WIDTH: usize = 90;
+const PARSE_COLOR: &str = "#bab0ac";
+const PART_1_COLOR: &str = "#4e79a7";
+const PART_2_COLOR: &str = "#f28e2b";
+
+/// Output formats supported by `cargo time --report`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReportFormat {
+    Csv,
+    Json,
+    Html,
+    Svg,
+}
+
+impl ReportFormat {
+    /// Default file extension of the format.
+    pub fn extension(self) -> &'static str {
+        match self {
+            ReportFormat::Csv => "csv",
+            ReportFormat::Json => "json",
+            ReportFormat::Html => "html",
+            ReportFormat::Svg => "svg",
+        }
+    }
+}
+
+impl FromStr for ReportFormat {
+    type Err = ReportFormatFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "csv" => Ok(ReportFormat::Csv),
+            "json" => Ok(ReportFormat::Json),
+            "html" => Ok(ReportFormat::Html),
+            "svg" => Ok(ReportFormat::Svg),
+            _ => Err(ReportFormatFromStrError),
+        }
+    }
+}
+
+/// An error which can be returned when parsing a [`ReportFormat`].
+#[derive(Debug)]
+pub struct ReportFormatFromStrError;
+
+impl std::error::Error for ReportFormatFromStrError {}
+
+impl Display for ReportFormatFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("expecting one of `csv`, `json`, `html` or `svg`")
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+fn part_nanos(part: Option<&String>) -> Option<f64> {
+    part.and_then(|s| parse_duration(s))
+}
+
+fn construct_csv(timings: &Timings) -> String {
+    let mut lines = vec![
+        "day,parse,parse_nanos,part_1,part_1_nanos,part_2,part_2_nanos,total_nanos".to_string(),
+    ];
+
+    for timing in &timings.data {
+        lines.push(format!(
+            "{},{},{},{},{},{},{},{}",
+            timing.day,
+            timing.parse.as_deref().unwrap_or_default(),
+            part_nanos(timing.parse.as_ref()).map_or(String::new(), |x| x.to_string()),
+            timing.part_1.as_deref().unwrap_or_default(),
+            part_nanos(timing.part_1.as_ref()).map_or(String::new(), |x| x.to_string()),
+            timing.part_2.as_deref().unwrap_or_default(),
+            part_nanos(timing.part_2.as_ref()).map_or(String::new(), |x| x.to_string()),
+            timing.total_nanos
+        ));
+    }
+
+    lines.push(String::new());
+    lines.join("\n")
+}
+
+fn construct_json(timings: &Timings) -> Result<String, io::Error> {
+    let days = timings
+        .data
+        .iter()
+        .map(|timing| {
+            let mut map: HashMap<String, JsonValue> = match JsonValue::from(timing) {
+                JsonValue::Object(map) => map,
+                _ => HashMap::new(),
+            };
+
+            for (key, part) in [
+                ("parse_nanos", &timing.parse),
+                ("part_1_nanos", &timing.part_1),
+                ("part_2_nanos", &timing.part_2),
+            ] {
+                map.insert(
+                    key.into(),
+                    part_nanos(part.as_ref()).map_or(JsonValue::Null, JsonValue::Number),
+                );
+            }
+
+            JsonValue::Object(map)
+        })
+        .collect();
+
+    let mut map: HashMap<String, JsonValue> = HashMap::new();
+    map.insert("data".into(), JsonValue::Array(days));
+    map.insert(
+        "total_millis".into(),
+        JsonValue::Number(timings.total_millis()),
+    );
+
+    JsonValue::Object(map)
+        .format()
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.to_string()))
+}
+
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn bar_width(nanos: Option<f64>, max_nanos: f64) -> usize {
+    nanos.map_or(0, |n| {
+        ((n / max_nanos) * CHART_WIDTH as f64).round() as usize
+    })
+}
+
+fn construct_svg(timings: &Timings) -> String {
+    let max_nanos = timings
+        .data
+        .iter()
+        .flat_map(|t| [&t.parse, &t.part_1, &t.part_2])
+        .filter_map(|part| part_nanos(part.as_ref()))
+        .fold(1_f64, f64::max);
+
+    let width = LABEL_WIDTH + CHART_WIDTH + VALUE_WIDTH;
+    let height = ROW_HEIGHT * timings.data.len() + 30;
+
+    let mut lines = vec![format!(
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="{height}" viewBox="0 0 {width} {height}" font-family="sans-serif" font-size="11">"#
+    )];
+
+    for (index, (label, color)) in [
+        ("Parse", PARSE_COLOR),
+        ("Part 1", PART_1_COLOR),
+        ("Part 2", PART_2_COLOR),
+    ]
+    .iter()
+    .enumerate()
+    {
+        let x = LABEL_WIDTH + index * 70;
+        lines.push(format!(
+            r#"  <rect x="{x}" y="4" width="10" height="10" fill="{color}" />"#
+        ));
+        lines.push(format!(r#"  <text x="{}" y="13">{label}</text>"#, x + 14));
+    }
+
+    for (index, timing) in timings.data.iter().enumerate() {
+        let y = 24 + index * ROW_HEIGHT;
+        let parts = [
+            (&timing.parse, y, PARSE_COLOR),
+            (&timing.part_1, y + BAR_HEIGHT + 2, PART_1_COLOR),
+            (&timing.part_2, y + 2 * (BAR_HEIGHT + 2), PART_2_COLOR),
+        ];
+
+        lines.push(format!(
+            r#"  <text x="0" y="{}">Day {}</text>"#,
+            y + BAR_HEIGHT + BAR_HEIGHT / 2 + 6,
+            timing.day.into_inner()
+        ));
+
+        for (part, bar_y, color) in parts {
+            let bar_width = bar_width(part_nanos(part.as_ref()), max_nanos);
+
+            lines.push(format!(
+                r#"  <rect x="{LABEL_WIDTH}" y="{bar_y}" width="{bar_width}" height="{BAR_HEIGHT}" fill="{color}" />"#
+            ));
+            lines.push(format!(
+                r#"  <text x="{}" y="{}">{}</text>"#,
+                LABEL_WIDTH + bar_width + 4,
+                bar_y + BAR_HEIGHT - 3,
+                part.as_deref().unwrap_or("-")
+            ));
+        }
+    }
+
+    lines.push("</svg>".into());
+    lines.push(String::new());
+    lines.join("\n")
+}
+
+fn construct_html(timings: &Timings) -> String {
+    let rows: Vec<String> = timings
+        .data
+        .iter()
+        .map(|timing: &Timing| {
+            format!(
+                "      <tr><td>Day {}</td><td>{}</td><td>{}</td><td>{}</td></tr>",
+                timing.day.into_inner(),
+                timing.parse.as_deref().unwrap_or("-"),
+                timing.part_1.as_deref().unwrap_or("-"),
+                timing.part_2.as_deref().unwrap_or("-")
+            )
+        })
+        .collect();
+
+    [
+        "<!DOCTYPE html>".to_string(),
+        "<html lang=\"en\">".into(),
+        "<head>".into(),
+        "  <meta charset=\"utf-8\">".into(),
+        "  <title>Benchmarks</title>".into(),
+        "  <style>body { font-family: sans-serif; margin: 2em; } table { border-collapse: collapse; margin-top: 1em; } td, th { border: 1px solid #ccc; padding: 0.25em 0.75em; text-align: center; }</style>".into(),
+        "</head>".into(),
+        "<body>".into(),
+        "  <h1>Benchmarks</h1>".into(),
+        construct_svg(timings),
+        "  <table>".into(),
+        "    <thead><tr><th>Day</th><th>Parse</th><th>Part 1</th><th>Part 2</th></tr></thead>".into(),
+        "    <tbody>".into(),
+        rows.join("\n"),
+        "    </tbody>".into(),
+        "  </table>".into(),
+        format!("  <p><strong>Total: {:.2}ms</strong></p>", timings.total_millis()),
+        "</body>".into(),
+        "</html>".into(),
+        String::new(),
+    ]
+    .join("\n")
+}
+
+/// Renders `timings` in the requested format.
+pub fn construct_report(timings: &Timings, format: ReportFormat) -> Result<String, io::Error> {
+    match format {
+        ReportFormat::Csv => Ok(construct_csv(timings)),
+        ReportFormat::Json => construct_json(timings),
+        ReportFormat::Html => Ok(construct_html(timings)),
+        ReportFormat::Svg => Ok(construct_svg(timings)),
+    }
+}
+
+/// Writes a report of `timings` to `path`.
+pub fn write(timings: &Timings, format: ReportFormat, path: &str) -> Result<(), io::Error> {
+    fs::write(path, construct_report(timings, format)?)
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::collections::HashMap;
+    use std::str::FromStr;
+
+    use tinyjson::JsonValue;
+
+    use super::{construct_report, ReportFormat};
+    use crate::{day, template::timings::Timing, template::timings::Timings};
+
+    fn get_mock_timings() -> Timings {
+        Timings {
+            data: vec![
+                Timing {
+                    day: day!(1),
+                    parse: Some("2.0ms".into()),
+                    part_1: Some("10.0ms".into()),
+                    part_2: Some("20.0ms".into()),
+                    total_nanos: 3.2e+7,
+                    hash: None,
+                },
+                Timing {
+                    day: day!(4),
+                    parse: None,
+                    part_1: Some("40.0µs".into()),
+                    part_2: None,
+                    total_nanos: 4e+4,
+                    hash: None,
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn parses_formats() {
+        assert_eq!(ReportFormat::from_str("CSV").unwrap(), ReportFormat::Csv);
+        assert_eq!(ReportFormat::from_str("svg").unwrap(), ReportFormat::Svg);
+        assert_eq!(ReportFormat::from_str("pdf").is_err(), true);
+    }
+
+    #[test]
+    fn formats_csv() {
+        let csv = construct_report(&get_mock_timings(), ReportFormat::Csv).unwrap();
+        let expected = [
+            "day,parse,parse_nanos,part_1,part_1_nanos,part_2,part_2_nanos,total_nanos",
+            "01,2.0ms,2000000,10.0ms,10000000,20.0ms,20000000,32000000",
+            "04,,,40.0µs,40000,,,40000",
+            "",
+        ]
+        .join("\n");
+        assert_eq!(csv, expected);
+    }
+
+    #[test]
+    fn formats_json() {
+        let json = construct_report(&get_mock_timings(), ReportFormat::Json).unwrap();
+        let value = JsonValue::from_str(&json).unwrap();
+        let data = value
+            .get::<HashMap<String, JsonValue>>()
+            .unwrap()
+            .get("data")
+            .unwrap()
+            .get::<Vec<JsonValue>>()
+            .unwrap();
+        assert_eq!(data.len(), 2);
+        let second = data[1].get::<HashMap<String, JsonValue>>().unwrap();
+        assert_eq!(
+            second.get("part_1_nanos").unwrap().get::<f64>(),
+            Some(&40000_f64)
+        );
+        assert_eq!(second.get("part_2_nanos").unwrap().is_null(), true);
+    }
+
+    #[test]
+    fn formats_svg() {
+        let svg = construct_report(&get_mock_timings(), ReportFormat::Svg).unwrap();
+        assert_eq!(svg.starts_with("<svg"), true);
+        assert_eq!(svg.matches("<rect").count(), 3 + 6);
+        // the longest bar spans the whole chart.
+        assert_eq!(svg.contains(r#"width="560""#), true);
+    }
+
+    #[test]
+    fn formats_html() {
+        let html = construct_report(&get_mock_timings(), ReportFormat::Html).unwrap();
+        assert_eq!(html.contains("<svg"), true);
+        assert_eq!(
+            html.contains("<td>Day 4</td><td>-</td><td>40.0µs</td><td>-</td>"),
+            true
+        );
+    }
+}
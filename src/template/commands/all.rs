@@ -1,5 +1,6 @@
-use crate::template::{all_days, run_multi::run_multi};
+use crate::template::{run_multi::run_multi, DayFilters, DaySet};
 
-pub fn handle(is_release: bool) {
-    run_multi(&all_days().collect(), is_release, false);
+pub fn handle(days: Option<DaySet>, filters: DayFilters, is_release: bool) {
+    let days = filters.apply(days.unwrap_or_else(DaySet::all));
+    run_multi(&days, is_release, false, false);
 }
@@ -1,14 +1,21 @@
-use crate::template::{aoc_cli, Day};
+use crate::template::{aoc_cli, Day, DaySet};
 use std::process;
 
 pub fn handle(day: Day) {
+    handle_set(&DaySet::from(day));
+}
+
+/// Downloads input and puzzle description of every day in `days`.
+pub fn handle_set(days: &DaySet) {
     if aoc_cli::check().is_err() {
         eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
         process::exit(1);
     }
 
-    if let Err(e) = aoc_cli::download(day) {
-        eprintln!("failed to call aoc-cli: {e}");
-        process::exit(1);
-    };
+    for day in days.iter() {
+        if let Err(e) = aoc_cli::download(day) {
+            eprintln!("failed to call aoc-cli: {e}");
+            process::exit(1);
+        };
+    }
 }
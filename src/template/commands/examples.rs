@@ -0,0 +1,140 @@
+use std::{fs, process};
+
+use crate::template::puzzle::{code_blocks, get_path_for_puzzle, proposed_answers, read_markdown};
+use crate::template::run_multi::get_path_for_bin;
+use crate::template::Day;
+
+const PREVIEW_LINES: usize = 4;
+static TEST_PLACEHOLDER: &str = "assert_eq!(result, None);";
+
+/// Path of the `index`-th (starting at 1) of `count` selected examples, matching `read_file` and `read_file_part`.
+fn example_path(day: Day, index: usize, count: usize) -> String {
+    if count == 1 {
+        format!("data/examples/{day}.txt")
+    } else {
+        format!("data/examples/{day}-{index}.txt")
+    }
+}
+
+fn print_blocks(blocks: &[String]) {
+    for (index, block) in blocks.iter().enumerate() {
+        let lines: Vec<&str> = block.lines().collect();
+        println!("[{}] {} line(s)", index + 1, lines.len());
+
+        for line in lines.iter().take(PREVIEW_LINES) {
+            println!("    {line}");
+        }
+        if lines.len() > PREVIEW_LINES {
+            println!("    ...");
+        }
+    }
+}
+
+fn write_examples(day: Day, blocks: &[String], selection: &[usize], overwrite: bool) {
+    for (index, block_index) in selection.iter().enumerate() {
+        let Some(block) = block_index.checked_sub(1).and_then(|i| blocks.get(i)) else {
+            eprintln!(
+                "There is no code block [{block_index}], expecting a value between 1 and {}.",
+                blocks.len()
+            );
+            process::exit(1);
+        };
+
+        let path = example_path(day, index + 1, selection.len());
+        // scaffold creates empty example files, these can be replaced safely.
+        let is_empty = fs::read_to_string(&path).map_or(true, |s| s.trim().is_empty());
+
+        if !is_empty && !overwrite {
+            eprintln!("Kept existing example file \"{path}\", pass `--overwrite` to replace it.");
+            continue;
+        }
+
+        match fs::write(&path, block) {
+            Ok(()) => println!("Wrote code block [{block_index}] to \"{path}\""),
+            Err(e) => {
+                eprintln!("Failed to write example file: {e}");
+                process::exit(1);
+            }
+        }
+    }
+}
+
+/// Replaces the `None` placeholders of the scaffolded tests with the proposed answers.
+fn update_tests(day: Day, answers: &[Option<String>; 2]) {
+    let path = get_path_for_bin(day);
+    let Ok(mut module) = fs::read_to_string(&path) else {
+        eprintln!("Module file \"{path}\" not found, scaffold the day first.");
+        process::exit(1);
+    };
+
+    for (part, answer) in answers.iter().enumerate() {
+        let test_fn = if part == 0 {
+            "fn test_part_one()"
+        } else {
+            "fn test_part_two()"
+        };
+
+        let Some(answer) = answer.as_ref().filter(|a| a.parse::<i64>().is_ok()) else {
+            continue;
+        };
+        let Some(test_start) = module.find(test_fn) else {
+            continue;
+        };
+        let test_end = module[test_start..]
+            .find("\n    }")
+            .map_or(module.len(), |i| test_start + i);
+        let Some(placeholder) = module[test_start..test_end].find(TEST_PLACEHOLDER) else {
+            continue;
+        };
+
+        let start = test_start + placeholder;
+        module.replace_range(
+            start..start + TEST_PLACEHOLDER.len(),
+            &format!("assert_eq!(result, Some({answer}));"),
+        );
+        println!("Updated `{test_fn}` to expect {answer}.");
+    }
+
+    if let Err(e) = fs::write(&path, module) {
+        eprintln!("Failed to update module file: {e}");
+        process::exit(1);
+    }
+}
+
+pub fn handle(day: Day, selection: Option<Vec<usize>>, overwrite: bool, update: bool) {
+    let Some(markdown) = read_markdown(day) else {
+        eprintln!(
+            "Puzzle description \"{}\" not found, run `cargo download {day}` first.",
+            get_path_for_puzzle(day)
+        );
+        process::exit(1);
+    };
+
+    let blocks = code_blocks(&markdown);
+    let answers = proposed_answers(&markdown);
+
+    match &selection {
+        Some(selection) => write_examples(day, &blocks, selection, overwrite),
+        None => {
+            if blocks.is_empty() {
+                println!("No code blocks found in \"{}\".", get_path_for_puzzle(day));
+            } else {
+                print_blocks(&blocks);
+                println!("---");
+                println!("Write code blocks with `cargo examples {day} --write <blocks>`, e.g. `--write 1` or `--write 1,3`.");
+            }
+        }
+    }
+
+    println!();
+    for (part, answer) in answers.iter().enumerate() {
+        match answer {
+            Some(answer) => println!("Proposed answer for part {}: {answer}", part + 1),
+            None => println!("No answer found for part {}.", part + 1),
+        }
+    }
+
+    if update {
+        update_tests(day, &answers);
+    }
+}
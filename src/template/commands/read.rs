@@ -1,15 +1,55 @@
-use std::process;
+use std::{env, process};
 
-use crate::template::{aoc_cli, Day};
+use crate::template::puzzle::{get_path_for_puzzle, read_markdown};
+use crate::template::{aoc_cli, markdown, Day};
 
-pub fn handle(day: Day) {
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
-        process::exit(1);
-    }
+const DEFAULT_WIDTH: usize = 80;
+const MAX_WIDTH: usize = 100;
 
-    if let Err(e) = aoc_cli::read(day) {
-        eprintln!("failed to call aoc-cli: {e}");
-        process::exit(1);
+fn terminal_width() -> usize {
+    env::var("COLUMNS")
+        .ok()
+        .and_then(|columns| columns.parse().ok())
+        .map_or(DEFAULT_WIDTH, |columns: usize| columns.min(MAX_WIDTH))
+}
+
+/// Renders the downloaded puzzle description, downloading it first if it is missing.
+pub fn handle(day: Day, part: Option<u8>) {
+    let markdown = match read_markdown(day) {
+        Some(markdown) => markdown,
+        None => {
+            if aoc_cli::check().is_err() {
+                eprintln!("Puzzle description \"{}\" not found and command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.", get_path_for_puzzle(day));
+                process::exit(1);
+            }
+
+            if let Err(e) = aoc_cli::download_puzzle(day) {
+                eprintln!("failed to call aoc-cli: {e}");
+                process::exit(1);
+            }
+            println!();
+
+            let Some(markdown) = read_markdown(day) else {
+                eprintln!(
+                    "Puzzle description \"{}\" not found.",
+                    get_path_for_puzzle(day)
+                );
+                process::exit(1);
+            };
+            markdown
+        }
     };
+
+    let section = match part {
+        Some(part) => match markdown::select_part(&markdown, part) {
+            Some(section) => section,
+            None => {
+                eprintln!("Part {part} is not unlocked yet, solve part one and run `cargo download {day}` to fetch it.");
+                process::exit(1);
+            }
+        },
+        None => &markdown,
+    };
+
+    println!("{}", markdown::render(section, terminal_width()));
 }
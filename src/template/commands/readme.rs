@@ -0,0 +1,20 @@
+use std::process;
+
+use crate::template::timings::Timings;
+use crate::template::{readme_benchmarks, readme_progress};
+
+pub fn handle() {
+    if let Err(e) = readme_progress::update() {
+        eprintln!("Failed to update readme progress: {e:?}");
+        process::exit(1);
+    }
+
+    let timings = Timings::read_from_file();
+
+    if !timings.data.is_empty() && readme_benchmarks::update(timings).is_err() {
+        eprintln!("Failed to update readme benchmarks.");
+        process::exit(1);
+    }
+
+    println!("🎄 Updated README.md.");
+}
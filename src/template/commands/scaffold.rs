@@ -1,79 +1,176 @@
-use std::{
-    fs::{File, OpenOptions},
-    io::Write,
-    process,
-};
+use std::{fs, path::Path, process};
 
-use crate::template::Day;
+use crate::template::{Day, DaySet};
 
 const MODULE_TEMPLATE: &str =
     include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/src/template.txt"));
 
-fn safe_create_file(path: &str, overwrite: bool) -> Result<File, std::io::Error> {
-    let mut file = OpenOptions::new();
-    if overwrite {
-        file.create(true);
-    } else {
-        file.create_new(true);
-    }
-    file.truncate(true).write(true).open(path)
+/// What scaffolding does with a single file.
+#[derive(Debug, PartialEq, Eq)]
+enum FileAction {
+    Create,
+    /// Overwrite the file after copying it to the contained backup path.
+    Overwrite(String),
+    Keep,
 }
 
-fn create_file(path: &str) -> Result<File, std::io::Error> {
-    OpenOptions::new()
-        .write(true)
-        .create(true)
-        .truncate(true)
-        .open(path)
+struct ScaffoldFile {
+    label: &'static str,
+    /// Whether `--overwrite` applies to the file, data files are never overwritten.
+    overwritable: bool,
+    path: String,
+    contents: String,
+    action: FileAction,
 }
 
-pub fn handle(day: Day, overwrite: bool) {
-    let input_path = format!("data/inputs/{day}.txt");
-    let example_path = format!("data/examples/{day}.txt");
-    let module_path = format!("src/bin/{day}.rs");
+/// Finds a backup path that is not taken yet, e.g. `src/bin/01.rs.bak` or `src/bin/01.rs.bak.2`.
+fn backup_path(path: &str) -> String {
+    let mut backup = format!("{path}.bak");
+    let mut index = 1;
 
-    let mut file = match safe_create_file(&module_path, overwrite) {
-        Ok(file) => file,
-        Err(e) => {
-            eprintln!("Failed to create module file: {e}");
-            process::exit(1);
-        }
+    while Path::new(&backup).exists() {
+        index += 1;
+        backup = format!("{path}.bak.{index}");
+    }
+
+    backup
+}
+
+/// Plans the files of a day. Existing files are kept, so scaffolding a range of days skips the days
+/// already scaffolded. Only the module file is overwritten, and only with `overwrite`.
+fn plan(day: Day, overwrite: bool) -> Vec<ScaffoldFile> {
+    let module = ScaffoldFile {
+        label: "module",
+        overwritable: true,
+        path: format!("src/bin/{day}.rs"),
+        contents: MODULE_TEMPLATE.replace("%DAY_NUMBER%", &day.into_inner().to_string()),
+        action: FileAction::Create,
+    };
+    let input = ScaffoldFile {
+        label: "input",
+        overwritable: false,
+        path: format!("data/inputs/{day}.txt"),
+        contents: String::new(),
+        action: FileAction::Create,
+    };
+    let example = ScaffoldFile {
+        label: "example",
+        overwritable: false,
+        path: format!("data/examples/{day}.txt"),
+        contents: String::new(),
+        action: FileAction::Create,
     };
 
-    match file.write_all(
-        MODULE_TEMPLATE
-            .replace("%DAY_NUMBER%", &day.into_inner().to_string())
-            .as_bytes(),
-    ) {
-        Ok(()) => {
-            println!("Created module file \"{}\"", &module_path);
-        }
-        Err(e) => {
-            eprintln!("Failed to write module contents: {e}");
-            process::exit(1);
+    [module, input, example]
+        .into_iter()
+        .map(|mut file| {
+            if Path::new(&file.path).exists() {
+                file.action = if overwrite && file.overwritable {
+                    FileAction::Overwrite(backup_path(&file.path))
+                } else {
+                    FileAction::Keep
+                };
+            }
+            file
+        })
+        .collect()
+}
+
+fn print_plan(files: &[ScaffoldFile]) {
+    for file in files {
+        match &file.action {
+            FileAction::Create => println!("Would create {} file \"{}\"", file.label, file.path),
+            FileAction::Overwrite(backup) => println!(
+                "Would overwrite {} file \"{}\", backing it up to \"{backup}\"",
+                file.label, file.path
+            ),
+            FileAction::Keep => {
+                println!("Would keep existing {} file \"{}\"", file.label, file.path)
+            }
         }
     }
+}
 
-    match create_file(&input_path) {
-        Ok(_) => {
-            println!("Created empty input file \"{}\"", &input_path);
-        }
-        Err(e) => {
-            eprintln!("Failed to create input file: {e}");
-            process::exit(1);
+fn apply_plan(files: &[ScaffoldFile]) -> Result<(), std::io::Error> {
+    for file in files {
+        match &file.action {
+            FileAction::Create => {
+                fs::write(&file.path, &file.contents)?;
+                println!("Created {} file \"{}\"", file.label, file.path);
+            }
+            FileAction::Overwrite(backup) => {
+                fs::copy(&file.path, backup)?;
+                fs::write(&file.path, &file.contents)?;
+                println!(
+                    "Overwrote {} file \"{}\", backed up to \"{backup}\"",
+                    file.label, file.path
+                );
+            }
+            FileAction::Keep => {
+                println!("Kept existing {} file \"{}\"", file.label, file.path);
+            }
         }
     }
 
-    match create_file(&example_path) {
-        Ok(_) => {
-            println!("Created empty example file \"{}\"", &example_path);
-        }
-        Err(e) => {
-            eprintln!("Failed to create example file: {e}");
+    Ok(())
+}
+
+pub fn handle(day: Day, overwrite: bool, dry_run: bool) {
+    handle_set(&DaySet::from(day), overwrite, dry_run);
+}
+
+/// Scaffolds every day in `days`. Existing module files are kept unless `overwrite` is set.
+pub fn handle_set(days: &DaySet, overwrite: bool, dry_run: bool) {
+    for day in days.iter() {
+        let files = plan(day, overwrite);
+
+        if dry_run {
+            print_plan(&files);
+        } else if let Err(e) = apply_plan(&files) {
+            eprintln!("Failed to scaffold day {day}: {e}");
             process::exit(1);
         }
     }
 
-    println!("---");
-    println!("🎄 Type `cargo solve {day}` to run your solution.");
+    if dry_run {
+        println!("---");
+        println!("Dry run, no files were written.");
+        return;
+    }
+
+    if let [day] = days.iter().collect::<Vec<_>>()[..] {
+        println!("---");
+        println!("🎄 Type `cargo solve {day}` to run your solution.");
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{plan, FileAction};
+    use crate::day;
+
+    fn actions(overwrite: bool) -> Vec<(&'static str, FileAction)> {
+        // day 1 has a module and an example in this repository.
+        plan(day!(1), overwrite)
+            .into_iter()
+            .filter(|file| file.label != "input")
+            .map(|file| (file.label, file.action))
+            .collect()
+    }
+
+    #[test]
+    fn keeps_existing_files() {
+        assert_eq!(
+            actions(false),
+            [("module", FileAction::Keep), ("example", FileAction::Keep)]
+        );
+    }
+
+    #[test]
+    fn overwrites_only_modules() {
+        let [(_, FileAction::Overwrite(backup)), (_, FileAction::Keep)] = &actions(true)[..] else {
+            panic!("expecting the module to be overwritten and the example to be kept");
+        };
+        assert_eq!(backup.starts_with("src/bin/01.rs.bak"), true);
+    }
 }
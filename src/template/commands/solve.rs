@@ -1,8 +1,23 @@
-use std::process::{Command, Stdio};
+use std::{
+    env, fs,
+    process::{self, Command, Stdio},
+};
 
-use crate::template::Day;
+use crate::template::{flamegraph, Day};
+
+pub fn handle(
+    day: Day,
+    release: bool,
+    dhat: bool,
+    profile_cpu: bool,
+    submit_part: Option<u8>,
+    params: &[String],
+) {
+    if profile_cpu {
+        handle_profile_cpu(day, params);
+        return;
+    }
 
-pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
     let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), day.to_string()];
 
     if dhat {
@@ -23,6 +38,8 @@ pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
         cmd_args.push(submit_part.to_string());
     }
 
+    cmd_args.extend(param_args(params));
+
     let mut cmd = Command::new("cargo")
         .args(&cmd_args)
         .stdout(Stdio::inherit())
@@ -32,3 +49,121 @@ pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
 
     cmd.wait().unwrap();
 }
+
+/// Forwards `--param <name>=<value>` overrides to a solution binary.
+fn param_args(params: &[String]) -> Vec<String> {
+    params
+        .iter()
+        .flat_map(|param| ["--param".to_string(), param.clone()])
+        .collect()
+}
+
+fn is_perf_available() -> bool {
+    Command::new("perf")
+        .arg("--version")
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .is_ok_and(|status| status.success())
+}
+
+/// Profile a solution with `perf` if present, falling back to the in-process sampler of the `cpu-profile` feature.
+/// Both write one profile per part.
+fn handle_profile_cpu(day: Day, params: &[String]) {
+    let day_padded = day.to_string();
+
+    if !is_perf_available() {
+        println!("perf not found, using the in-process sampler.");
+
+        let status = Command::new("cargo")
+            .args([
+                "run",
+                "--bin",
+                &day_padded,
+                "--profile",
+                "profiling",
+                "--features",
+                "cpu-profile",
+                "--",
+                "--profile-cpu",
+            ])
+            .args(param_args(params))
+            .status()
+            .unwrap();
+
+        if !status.success() {
+            process::exit(1);
+        }
+        return;
+    }
+
+    let status = Command::new("cargo")
+        .args(["build", "--bin", &day_padded, "--profile", "profiling"])
+        .status()
+        .unwrap();
+
+    if !status.success() {
+        process::exit(1);
+    }
+
+    if let Err(e) = fs::create_dir_all("profiles") {
+        eprintln!("Failed to create profiles directory: {e}");
+        process::exit(1);
+    }
+
+    let target_dir = env::var("CARGO_TARGET_DIR").unwrap_or_else(|_| "target".into());
+    let bin_path = format!("{target_dir}/profiling/{day}");
+
+    for part in 1..=2 {
+        if let Err(e) = record_part(&bin_path, day, part, params) {
+            eprintln!("{e}");
+            process::exit(1);
+        }
+    }
+}
+
+/// Records a profile of one part with `perf`, the other part only runs once.
+fn record_part(bin_path: &str, day: Day, part: u8, params: &[String]) -> Result<(), String> {
+    let name = format!("{day}-{part}");
+    let data_path = format!("profiles/{name}.perf.data");
+
+    let status = Command::new("perf")
+        .args([
+            "record",
+            "--freq",
+            "997",
+            "--call-graph",
+            "dwarf",
+            "--output",
+            &data_path,
+            "--",
+            bin_path,
+            "--profile-cpu",
+            "--profile-part",
+            &part.to_string(),
+        ])
+        .args(param_args(params))
+        .status()
+        .unwrap();
+
+    if !status.success() {
+        return Err("perf exited with a non-zero status.".into());
+    }
+
+    let script = match Command::new("perf")
+        .args(["script", "--input", &data_path])
+        .stderr(Stdio::null())
+        .output()
+    {
+        Ok(output) if output.status.success() => output.stdout,
+        _ => return Err(format!("Failed to read samples from \"{data_path}\".")),
+    };
+
+    let stacks = flamegraph::fold_perf_script(&String::from_utf8_lossy(&script));
+
+    let (folded_path, svg_path) = flamegraph::write_profile(&name, &stacks)
+        .map_err(|e| format!("Failed to write cpu profile: {e}"))?;
+    println!("Wrote \"{folded_path}\" and \"{svg_path}\".");
+
+    Ok(())
+}
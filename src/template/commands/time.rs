@@ -1,30 +1,55 @@
-use std::collections::HashSet;
-
+use crate::template::benchmark_report::{self, ReportFormat};
 use crate::template::run_multi::run_multi;
+use crate::template::source_hash::day_hash;
 use crate::template::timings::Timings;
-use crate::template::{all_days, readme_benchmarks, Day};
+use crate::template::{readme_benchmarks, DayFilters, DaySet};
 
-pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
+pub fn handle(
+    days: Option<DaySet>,
+    filters: DayFilters,
+    run_all: bool,
+    store: bool,
+    report: Option<ReportFormat>,
+    out: Option<String>,
+    variants: bool,
+) {
     let stored_timings = Timings::read_from_file();
 
-    let days_to_run = day.map_or_else(
-        || {
-            if run_all {
-                all_days().collect()
-            } else {
-                // when the `--all` flag is not set, filter out days that are fully benched.
-                all_days()
-                    .filter(|day| !stored_timings.is_day_complete(*day))
-                    .collect()
-            }
-        },
-        |day| HashSet::from([day]),
-    );
+    let days_to_run = days.unwrap_or_else(|| {
+        let mut days = DaySet::all();
+        // when neither days nor the `--all` flag are set, filter out days that are fully benched
+        // with their current code and input.
+        if !run_all && filters.slowest.is_none() {
+            days.retain(|day| !stored_timings.is_day_up_to_date(*day, &day_hash(*day)));
+        }
+        days
+    });
+    let days_to_run = filters.apply(days_to_run);
+
+    let timings = run_multi(&days_to_run, true, true, variants).unwrap();
+
+    // variant comparisons are not representative of the solution, do not persist them.
+    if variants {
+        return;
+    }
+
+    let merged_timings = stored_timings.merge(&timings);
 
-    let timings = run_multi(&days_to_run, true, true).unwrap();
+    if let Some(format) = report {
+        let path = out.unwrap_or_else(|| format!("data/benchmarks.{}", format.extension()));
+
+        println!();
+        match benchmark_report::write(&merged_timings, format, &path) {
+            Ok(()) => {
+                println!("Wrote benchmark report to \"{path}\".");
+            }
+            Err(e) => {
+                eprintln!("Failed to write benchmark report: {e}");
+            }
+        }
+    }
 
     if store {
-        let merged_timings = stored_timings.merge(&timings);
         merged_timings.store_file().unwrap();
 
         println!();
@@ -5,10 +5,57 @@ use std::str::FromStr;
 #[cfg(feature = "today")]
 use chrono::{Datelike, FixedOffset, Utc};
 
+use crate::template::aoc_cli;
+
 #[cfg(feature = "today")]
 const SERVER_UTC_OFFSET: i32 = -5;
 
-/// A valid day number of advent (i.e. an integer in range 1 to 25).
+/// Number of days of the longest calendar, used when no year is configured.
+pub const MAX_DAY: u8 = 25;
+
+/// Length of the calendar by first year it applies to, the last entry applies to every later year.
+const CALENDARS: &[(u16, u8)] = &[(2015, 25), (2025, 12)];
+
+/// Returns the number of puzzles of the event in `year`.
+pub const fn days_in_year(year: u16) -> u8 {
+    let mut days = MAX_DAY;
+    let mut i = 0;
+    while i < CALENDARS.len() {
+        if year >= CALENDARS[i].0 {
+            days = CALENDARS[i].1;
+        }
+        i += 1;
+    }
+    days
+}
+
+/// Returns the last day of the configured year, or [`MAX_DAY`] if `AOC_YEAR` is not set.
+pub fn last_day() -> u8 {
+    aoc_cli::get_year().map_or(MAX_DAY, days_in_year)
+}
+
+// Not part of the public API
+#[doc(hidden)]
+pub const fn __last_day_const(year: Option<&str>) -> u8 {
+    let Some(year) = year else {
+        return MAX_DAY;
+    };
+
+    let bytes = year.as_bytes();
+    let mut value: u16 = 0;
+    let mut i = 0;
+    while i < bytes.len() {
+        if !bytes[i].is_ascii_digit() || i >= 4 {
+            return MAX_DAY;
+        }
+        value = value * 10 + (bytes[i] - b'0') as u16;
+        i += 1;
+    }
+
+    days_in_year(value)
+}
+
+/// A valid day number of advent (i.e. an integer in range 1 to the last day of the configured year).
 ///
 /// # Display
 /// This value displays as a two digit number.
@@ -22,10 +69,20 @@ const SERVER_UTC_OFFSET: i32 = -5;
 pub struct Day(u8);
 
 impl Day {
-    /// Creates a [`Day`] from the provided value if it's in the valid range,
+    /// Creates a [`Day`] from the provided value if it's in the valid range of the configured year,
     /// returns [`None`] otherwise.
     pub fn new(day: u8) -> Option<Self> {
-        if day == 0 || day > 25 {
+        Self::new_in_range(day, last_day())
+    }
+
+    /// Creates a [`Day`] from the provided value if it's in the valid range of `year`,
+    /// returns [`None`] otherwise.
+    pub fn new_for_year(day: u8, year: u16) -> Option<Self> {
+        Self::new_in_range(day, days_in_year(year))
+    }
+
+    fn new_in_range(day: u8, last_day: u8) -> Option<Self> {
+        if day == 0 || day > last_day {
             return None;
         }
         Some(Self(day))
@@ -45,15 +102,20 @@ impl Day {
 
 #[cfg(feature = "today")]
 impl Day {
-    /// Returns the current day if it's between the 1st and the 25th of december, `None` otherwise.
+    /// Returns the current day if an event is running, `None` otherwise.
     pub fn today() -> Option<Self> {
         let offset = FixedOffset::east_opt(SERVER_UTC_OFFSET * 3600)?;
         let today = Utc::now().with_timezone(&offset);
-        if today.month() == 12 && today.day() <= 25 {
-            Self::new(u8::try_from(today.day()).ok()?)
-        } else {
-            None
+        if today.month() != 12 {
+            return None;
         }
+        let year = u16::try_from(today.year()).ok()?;
+        Self::new_for_year(u8::try_from(today.day()).ok()?, year)
+    }
+
+    /// Returns the number of puzzles of the current year's event.
+    pub fn days_in_current_event() -> u8 {
+        u16::try_from(Utc::now().year()).map_or(MAX_DAY, days_in_year)
     }
 }
 
@@ -81,39 +143,54 @@ impl FromStr for Day {
     type Err = DayFromStrError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let day = s.parse().map_err(|_| DayFromStrError)?;
-        Self::new(day).ok_or(DayFromStrError)
+        let last_day = last_day();
+        let error = DayFromStrError { last_day };
+        let day = s.parse().map_err(|_| error)?;
+        Self::new_in_range(day, last_day).ok_or(error)
     }
 }
 
 /// An error which can be returned when parsing a [`Day`].
-#[derive(Debug)]
-pub struct DayFromStrError;
+#[derive(Debug, Clone, Copy)]
+pub struct DayFromStrError {
+    /// The last day of the configured year.
+    pub last_day: u8,
+}
 
 impl Error for DayFromStrError {}
 
 impl Display for DayFromStrError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.write_str("expecting a day number between 1 and 25")
+        write!(f, "expecting a day number between 1 and {}", self.last_day)
     }
 }
 
 /* -------------------------------------------------------------------------- */
 
-/// An iterator that yields every day of advent from the 1st to the 25th.
+/// An iterator that yields every day of advent of the configured year.
 pub fn all_days() -> AllDays {
     AllDays::new()
 }
 
-/// An iterator that yields every day of advent from the 1st to the 25th.
+/// An iterator that yields every day of advent of `year`.
+pub fn all_days_in_year(year: u16) -> AllDays {
+    AllDays::until(days_in_year(year))
+}
+
+/// An iterator that yields every day of advent from the 1st to the last day of a year.
 pub struct AllDays {
     current: u8,
+    last: u8,
 }
 
 impl AllDays {
     #[allow(clippy::new_without_default)]
     pub fn new() -> Self {
-        Self { current: 1 }
+        Self::until(last_day())
+    }
+
+    fn until(last: u8) -> Self {
+        Self { current: 1, last }
     }
 }
 
@@ -121,10 +198,10 @@ impl Iterator for AllDays {
     type Item = Day;
 
     fn next(&mut self) -> Option<Self::Item> {
-        if self.current > 25 {
+        if self.current > self.last {
             return None;
         }
-        // NOTE: the iterator starts at 1 and we have verified that the value is not above 25.
+        // NOTE: the iterator starts at 1 and we have verified that the value is not above the last day.
         let day = Day(self.current);
         self.current += 1;
 
@@ -135,15 +212,16 @@ impl Iterator for AllDays {
 /* -------------------------------------------------------------------------- */
 
 /// Creates a [`Day`] value in a const context.
+/// The day is checked against the calendar of the `AOC_YEAR` set at compile time.
 #[macro_export]
 macro_rules! day {
     ($day:expr) => {{
         const _ASSERT: () = assert!(
-            $day != 0 && $day <= 25,
+            $day != 0 && $day <= $crate::template::__last_day_const(option_env!("AOC_YEAR")),
             concat!(
                 "invalid day number `",
                 $day,
-                "`, expecting a value between 1 and 25"
+                "`, expecting a value within the calendar of AOC_YEAR"
             ),
         );
         $crate::template::Day::__new_unchecked($day)
@@ -154,7 +232,7 @@ macro_rules! day {
 
 #[cfg(feature = "test_lib")]
 mod tests {
-    use super::{all_days, Day};
+    use super::{__last_day_const, all_days, all_days_in_year, days_in_year, Day};
 
     #[test]
     fn all_days_iterator() {
@@ -187,6 +265,32 @@ mod tests {
         assert_eq!(iter.next(), Some(Day(25)));
         assert_eq!(iter.next(), None);
     }
+
+    #[test]
+    fn calendar_per_year() {
+        assert_eq!(days_in_year(2015), 25);
+        assert_eq!(days_in_year(2024), 25);
+        assert_eq!(days_in_year(2025), 12);
+        assert_eq!(days_in_year(2030), 12);
+        assert_eq!(all_days_in_year(2025).last(), Some(Day(12)));
+        assert_eq!(all_days_in_year(2024).count(), 25);
+    }
+
+    #[test]
+    fn validates_day_for_year() {
+        assert_eq!(Day::new_for_year(12, 2025), Some(Day(12)));
+        assert_eq!(Day::new_for_year(13, 2025), None);
+        assert_eq!(Day::new_for_year(25, 2024), Some(Day(25)));
+        assert_eq!(Day::new_for_year(0, 2024), None);
+    }
+
+    #[test]
+    fn parses_year_at_compile_time() {
+        assert_eq!(__last_day_const(Some("2025")), 12);
+        assert_eq!(__last_day_const(Some("2023")), 25);
+        assert_eq!(__last_day_const(Some("twenty")), 25);
+        assert_eq!(__last_day_const(None), 25);
+    }
 }
 
 /* -------------------------------------------------------------------------- */
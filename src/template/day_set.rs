@@ -0,0 +1,301 @@
+/// Module that selects the days a multi-day command operates on.
+/// Days are given as a list of days and ranges like `1-5,8,12-`, and can be narrowed down with [`DayFilters`].
+use std::collections::BTreeSet;
+use std::error::Error;
+use std::fmt::Display;
+use std::str::FromStr;
+
+use crate::template::puzzle::Puzzle;
+use crate::template::source_hash;
+use crate::template::timings::Timings;
+use crate::template::{all_days, last_day, Day};
+
+/// A sorted set of days, parsed from expressions like `1-5,8,12-`.
+///
+/// Ranges are inclusive and may be open on either end, open ends extend to the first or last day of the
+/// configured year.
+///
+/// ```
+/// # use advent_of_code::template::DaySet;
+/// let days: DaySet = "1-3,8".parse().unwrap();
+/// assert_eq!(days.iter().map(|d| d.into_inner()).collect::<Vec<_>>(), vec![1, 2, 3, 8]);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DaySet(BTreeSet<Day>);
+
+impl DaySet {
+    /// Every day of the configured year.
+    pub fn all() -> Self {
+        all_days().collect()
+    }
+
+    pub fn contains(&self, day: Day) -> bool {
+        self.0.contains(&day)
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Iterates over the days in ascending order.
+    pub fn iter(&self) -> impl Iterator<Item = Day> + '_ {
+        self.0.iter().copied()
+    }
+
+    pub fn retain(&mut self, f: impl FnMut(&Day) -> bool) {
+        self.0.retain(f);
+    }
+}
+
+impl FromIterator<Day> for DaySet {
+    fn from_iter<T: IntoIterator<Item = Day>>(iter: T) -> Self {
+        Self(iter.into_iter().collect())
+    }
+}
+
+impl From<Day> for DaySet {
+    fn from(day: Day) -> Self {
+        Self(BTreeSet::from([day]))
+    }
+}
+
+fn parse_day(s: &str) -> Result<Day, DaySetFromStrError> {
+    s.trim()
+        .parse()
+        .map_err(|_| DaySetFromStrError::InvalidDay(s.trim().to_string()))
+}
+
+impl FromStr for DaySet {
+    type Err = DaySetFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut days = BTreeSet::new();
+
+        for item in s.split(',') {
+            let item = item.trim();
+            if item.is_empty() {
+                return Err(DaySetFromStrError::Empty);
+            }
+
+            match item.split_once('-') {
+                Some((start, end)) => {
+                    let start = if start.trim().is_empty() {
+                        1
+                    } else {
+                        parse_day(start)?.into_inner()
+                    };
+                    let end = if end.trim().is_empty() {
+                        last_day()
+                    } else {
+                        parse_day(end)?.into_inner()
+                    };
+
+                    if start > end {
+                        return Err(DaySetFromStrError::InvalidRange(item.to_string()));
+                    }
+
+                    days.extend((start..=end).filter_map(Day::new));
+                }
+                None => {
+                    days.insert(parse_day(item)?);
+                }
+            }
+        }
+
+        Ok(Self(days))
+    }
+}
+
+impl Display for DaySet {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let days: Vec<u8> = self.iter().map(Day::into_inner).collect();
+        let mut ranges: Vec<String> = vec![];
+        let mut i = 0;
+
+        while i < days.len() {
+            let mut j = i;
+            while j + 1 < days.len() && days[j + 1] == days[j] + 1 {
+                j += 1;
+            }
+
+            ranges.push(if i == j {
+                days[i].to_string()
+            } else {
+                format!("{}-{}", days[i], days[j])
+            });
+            i = j + 1;
+        }
+
+        f.write_str(&ranges.join(","))
+    }
+}
+
+/// An error which can be returned when parsing a [`DaySet`].
+#[derive(Debug)]
+pub enum DaySetFromStrError {
+    Empty,
+    InvalidDay(String),
+    InvalidRange(String),
+}
+
+impl Error for DaySetFromStrError {}
+
+impl Display for DaySetFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DaySetFromStrError::Empty => f.write_str("expecting a list of days like `1-5,8,12-`"),
+            DaySetFromStrError::InvalidDay(s) => write!(
+                f,
+                "invalid day `{s}`, expecting a day number between 1 and {}",
+                last_day()
+            ),
+            DaySetFromStrError::InvalidRange(s) => {
+                write!(f, "invalid range `{s}`, the start is after the end")
+            }
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Filters that narrow down a [`DaySet`], shared by the `--unsolved`, `--changed` and `--slowest` flags.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DayFilters {
+    /// Keep days that do not have two stars in their downloaded puzzle description.
+    pub unsolved: bool,
+    /// Keep days whose solution, input or library changed since their timings were stored.
+    pub changed: bool,
+    /// Keep the `n` days with the slowest stored timings.
+    pub slowest: Option<usize>,
+}
+
+impl DayFilters {
+    /// Applies the filters to `days`.
+    pub fn apply(&self, mut days: DaySet) -> DaySet {
+        if self.unsolved {
+            days.retain(|day| Puzzle::read_from_file(*day).is_none_or(|p| p.stars() < 2));
+        }
+
+        if self.changed || self.slowest.is_some() {
+            let timings = Timings::read_from_file();
+
+            if self.changed {
+                days = changed_days(&timings, &days);
+            }
+
+            if let Some(n) = self.slowest {
+                days = slowest_days(&timings, &days, n);
+            }
+        }
+
+        days
+    }
+}
+
+/// Returns the `n` days of `days` with the highest stored total time.
+fn slowest_days(timings: &Timings, days: &DaySet, n: usize) -> DaySet {
+    let mut timed: Vec<_> = timings
+        .data
+        .iter()
+        .filter(|t| days.contains(t.day))
+        .collect();
+
+    timed.sort_by(|a, b| b.total_nanos.total_cmp(&a.total_nanos));
+    timed.iter().take(n).map(|t| t.day).collect()
+}
+
+/// Days without timings measured with their current solution, input and library, see [`source_hash`].
+fn changed_days(timings: &Timings, days: &DaySet) -> DaySet {
+    days.iter()
+        .filter(|day| !timings.is_day_up_to_date(*day, &source_hash::day_hash(*day)))
+        .collect()
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::str::FromStr;
+
+    use super::{changed_days, slowest_days, DaySet};
+    use crate::day;
+    use crate::template::source_hash::day_hash;
+    use crate::template::timings::{Timing, Timings};
+
+    fn days(s: &str) -> Vec<u8> {
+        DaySet::from_str(s)
+            .unwrap()
+            .iter()
+            .map(|d| d.into_inner())
+            .collect()
+    }
+
+    #[test]
+    fn parses_lists_and_ranges() {
+        assert_eq!(days("8"), vec![8]);
+        assert_eq!(days("1-3, 8,2"), vec![1, 2, 3, 8]);
+        assert_eq!(days("-2,24-"), vec![1, 2, 24, 25]);
+    }
+
+    #[test]
+    fn rejects_invalid_sets() {
+        assert_eq!(DaySet::from_str("").is_err(), true);
+        assert_eq!(DaySet::from_str("1,,2").is_err(), true);
+        assert_eq!(DaySet::from_str("26").is_err(), true);
+        assert_eq!(DaySet::from_str("5-1").is_err(), true);
+        assert_eq!(DaySet::from_str("a-3").is_err(), true);
+    }
+
+    #[test]
+    fn displays_compact_ranges() {
+        let set = DaySet::from_str("1-5,8,12,13").unwrap();
+        assert_eq!(set.to_string(), "1-5,8,12-13");
+    }
+
+    #[test]
+    fn selects_slowest_days() {
+        let timing = |day, total_nanos| Timing {
+            day,
+            parse: None,
+            part_1: Some("1ms".into()),
+            part_2: Some("1ms".into()),
+            total_nanos,
+            hash: None,
+        };
+        let timings = Timings {
+            data: vec![
+                timing(day!(1), 10.0),
+                timing(day!(2), 30.0),
+                timing(day!(3), 20.0),
+                timing(day!(4), 40.0),
+            ],
+        };
+
+        let selected = slowest_days(&timings, &DaySet::from_str("1-3").unwrap(), 2);
+        assert_eq!(selected, DaySet::from_str("2-3").unwrap());
+    }
+
+    #[test]
+    fn selects_changed_days() {
+        let timing = |day, hash: Option<String>| Timing {
+            day,
+            parse: None,
+            part_1: Some("1ms".into()),
+            part_2: Some("1ms".into()),
+            total_nanos: 1.0,
+            hash,
+        };
+        let timings = Timings {
+            data: vec![
+                timing(day!(1), Some(day_hash(day!(1)))),
+                timing(day!(2), Some("outdated".into())),
+                timing(day!(3), None),
+            ],
+        };
+
+        let selected = changed_days(&timings, &DaySet::from_str("1-4").unwrap());
+        assert_eq!(selected, DaySet::from_str("2-4").unwrap());
+    }
+}
@@ -0,0 +1,257 @@
+/// Module that turns sampled call stacks into folded-stack files and flamegraph SVGs.
+/// Stacks are represented in the "folded" format popularized by `flamegraph.pl`: frames joined by `;`, root first.
+use std::{collections::BTreeMap, fs, io};
+
+static PROFILES_DIR: &str = "profiles";
+
+const WIDTH: f64 = 1200.0;
+const FRAME_HEIGHT: f64 = 16.0;
+const TITLE_HEIGHT: f64 = 30.0;
+const MIN_FRAME_WIDTH: f64 = 0.1;
+
+/// A folded stack and the number of samples it was observed in.
+pub type FoldedStack = (String, u64);
+
+/// Collapses the output of `perf script` into folded stacks.
+pub fn fold_perf_script(script: &str) -> Vec<FoldedStack> {
+    let mut stacks: BTreeMap<String, u64> = BTreeMap::new();
+    let mut command: Option<&str> = None;
+    let mut frames: Vec<String> = vec![];
+
+    for line in script.lines().chain(std::iter::once("")) {
+        if line.trim().is_empty() {
+            if let Some(command) = command.take() {
+                let stack = std::iter::once(command.to_string())
+                    .chain(frames.drain(..).rev())
+                    .collect::<Vec<_>>()
+                    .join(";");
+                *stacks.entry(stack).or_insert(0) += 1;
+            }
+            frames.clear();
+        } else if line.starts_with(char::is_whitespace) {
+            if let Some(frame) = parse_perf_frame(line) {
+                frames.push(frame);
+            }
+        } else if !line.starts_with('#') {
+            command = line.split_whitespace().next();
+        }
+    }
+
+    stacks.into_iter().collect()
+}
+
+/// Extracts the symbol from a stack line like `55d1c0a0b1c2 day_06::contains_loop+0x42 (/path/to/06)`.
+fn parse_perf_frame(line: &str) -> Option<String> {
+    let (_, rest) = line.trim().split_once(char::is_whitespace)?;
+    let symbol = match rest.rfind(" (") {
+        Some(index) => &rest[..index],
+        None => rest,
+    };
+    let symbol = match symbol.rfind("+0x") {
+        Some(index) => &symbol[..index],
+        None => symbol,
+    };
+
+    Some(symbol.trim().replace(';', ":"))
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[derive(Debug, Default)]
+struct Frame {
+    name: String,
+    samples: u64,
+    children: Vec<Frame>,
+}
+
+impl Frame {
+    fn insert(&mut self, path: &[&str], samples: u64) {
+        self.samples += samples;
+
+        if let Some((first, rest)) = path.split_first() {
+            let index = match self.children.iter().position(|c| c.name == *first) {
+                Some(index) => index,
+                None => {
+                    self.children.push(Frame {
+                        name: (*first).to_string(),
+                        ..Frame::default()
+                    });
+                    self.children.len() - 1
+                }
+            };
+            self.children[index].insert(rest, samples);
+        }
+    }
+
+    fn depth(&self) -> usize {
+        1 + self.children.iter().map(Frame::depth).max().unwrap_or(0)
+    }
+}
+
+fn escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Picks a stable, warm color for a frame name.
+fn frame_color(name: &str) -> String {
+    let hash = name
+        .bytes()
+        .fold(5381_u32, |hash, b| hash.wrapping_mul(33) ^ u32::from(b));
+    let red = 205 + hash % 50;
+    let green = 80 + (hash >> 8) % 150;
+    let blue = (hash >> 16) % 55;
+    format!("rgb({red},{green},{blue})")
+}
+
+#[allow(clippy::cast_precision_loss)]
+fn render_frame(
+    frame: &Frame,
+    x: f64,
+    depth: usize,
+    height: f64,
+    total: u64,
+    lines: &mut Vec<String>,
+) {
+    let width = frame.samples as f64 / total as f64 * WIDTH;
+    if width < MIN_FRAME_WIDTH {
+        return;
+    }
+
+    let y = height - (depth as f64 + 1.0) * FRAME_HEIGHT;
+    let percent = frame.samples as f64 / total as f64 * 100.0;
+    let name = escape(&frame.name);
+    // roughly 7px per character at the chosen font size.
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let max_chars = ((width - 6.0) / 7.0).max(0.0) as usize;
+    let label: String = if frame.name.chars().count() <= max_chars {
+        name.clone()
+    } else if max_chars > 2 {
+        escape(&frame.name.chars().take(max_chars - 2).collect::<String>()) + ".."
+    } else {
+        String::new()
+    };
+
+    lines.push(format!(
+        r#"  <g><title>{name} ({} samples, {percent:.2}%)</title><rect x="{x:.2}" y="{y:.2}" width="{width:.2}" height="{:.2}" fill="{}" rx="2" /><text x="{:.2}" y="{:.2}">{label}</text></g>"#,
+        frame.samples,
+        FRAME_HEIGHT - 1.0,
+        frame_color(&frame.name),
+        x + 3.0,
+        y + FRAME_HEIGHT - 4.0,
+    ));
+
+    let mut child_x = x;
+    for child in &frame.children {
+        render_frame(child, child_x, depth + 1, height, total, lines);
+        child_x += child.samples as f64 / total as f64 * WIDTH;
+    }
+}
+
+/// Renders folded stacks as a flamegraph, with the root at the bottom.
+#[allow(clippy::cast_precision_loss)]
+pub fn render_svg(stacks: &[FoldedStack], title: &str) -> String {
+    let mut root = Frame {
+        name: "all".into(),
+        ..Frame::default()
+    };
+
+    for (stack, samples) in stacks {
+        let path: Vec<&str> = stack.split(';').collect();
+        root.insert(&path, *samples);
+    }
+
+    let height = root.depth() as f64 * FRAME_HEIGHT + TITLE_HEIGHT;
+    let mut lines = vec![
+        format!(
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{WIDTH}" height="{height}" viewBox="0 0 {WIDTH} {height}" font-family="monospace" font-size="12">"#
+        ),
+        format!(r##"  <rect width="{WIDTH}" height="{height}" fill="#f8f8f8" />"##),
+        format!(
+            r#"  <text x="{}" y="20" font-size="16" text-anchor="middle">{}</text>"#,
+            WIDTH / 2.0,
+            escape(title)
+        ),
+    ];
+
+    if root.samples > 0 {
+        render_frame(&root, 0.0, 0, height, root.samples, &mut lines);
+    }
+
+    lines.push("</svg>".into());
+    lines.push(String::new());
+    lines.join("\n")
+}
+
+/// Writes folded stacks and the rendered flamegraph to `profiles/<name>.folded` and `profiles/<name>.svg`.
+/// Returns the paths of both files.
+pub fn write_profile(name: &str, stacks: &[FoldedStack]) -> Result<(String, String), io::Error> {
+    fs::create_dir_all(PROFILES_DIR)?;
+
+    let folded_path = format!("{PROFILES_DIR}/{name}.folded");
+    let svg_path = format!("{PROFILES_DIR}/{name}.svg");
+
+    let folded: String = stacks
+        .iter()
+        .map(|(stack, samples)| format!("{stack} {samples}\n"))
+        .collect();
+
+    fs::write(&folded_path, folded)?;
+    fs::write(
+        &svg_path,
+        render_svg(stacks, &format!("CPU profile: {name}")),
+    )?;
+
+    Ok((folded_path, svg_path))
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{fold_perf_script, render_svg};
+
+    const SCRIPT: &str = "06 12345 1234.567890:     1010101 cycles:u:
+\t    55d1c0a0b1c2 _06::contains_loop+0x42 (/target/profiling/06)
+\t    55d1c0a0b1c3 _06::main+0x12 (/target/profiling/06)
+
+06 12345 1234.567891:     1010101 cycles:u:
+\t    55d1c0a0b1c2 _06::next_position+0x2 (/target/profiling/06)
+\t    55d1c0a0b1c2 _06::contains_loop+0x42 (/target/profiling/06)
+\t    55d1c0a0b1c3 _06::main+0x12 (/target/profiling/06)
+
+06 12345 1234.567892:     1010101 cycles:u:
+\t    55d1c0a0b1c2 _06::contains_loop+0x40 (/target/profiling/06)
+\t    55d1c0a0b1c3 _06::main+0x12 (/target/profiling/06)
+";
+
+    #[test]
+    fn folds_perf_script() {
+        let folded = fold_perf_script(SCRIPT);
+        assert_eq!(
+            folded,
+            vec![
+                ("06;_06::main;_06::contains_loop".to_string(), 2),
+                (
+                    "06;_06::main;_06::contains_loop;_06::next_position".to_string(),
+                    1
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn renders_flamegraph() {
+        let svg = render_svg(&fold_perf_script(SCRIPT), "CPU profile: 06");
+        assert_eq!(svg.starts_with("<svg"), true);
+        // all, 06, main, contains_loop, next_position
+        assert_eq!(svg.matches("<g>").count(), 5);
+        assert_eq!(svg.contains("_06::next_position (1 samples, 33.33%)"), true);
+    }
+
+    #[test]
+    fn renders_empty_flamegraph() {
+        let svg = render_svg(&[], "empty");
+        assert_eq!(svg.matches("<g>").count(), 0);
+    }
+}
@@ -0,0 +1,285 @@
+/// Module that renders puzzle descriptions, as downloaded by aoc-cli, in the terminal.
+/// Supports the subset of Markdown used by puzzles: headings, emphasis, inline code, links, lists and code blocks.
+use crate::template::{ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+
+pub(super) static CODE_FENCE: &str = "```";
+pub(super) static PART_TWO_HEADING: &str = "--- Part Two ---";
+const CODE_INDENT: &str = "    ";
+
+/// Returns the section of a puzzle description for `part`, `None` if the part is not unlocked yet.
+pub fn select_part(markdown: &str, part: u8) -> Option<&str> {
+    let part_two = markdown
+        .find(PART_TWO_HEADING)
+        .map(|i| markdown[..i].rfind('\n').map_or(0, |j| j + 1));
+
+    match (part, part_two) {
+        (1, Some(start)) => Some(&markdown[..start]),
+        (1, None) => Some(markdown),
+        (2, Some(start)) => Some(&markdown[start..]),
+        _ => None,
+    }
+}
+
+#[derive(Default)]
+struct Style {
+    bold: bool,
+    italic: bool,
+}
+
+impl Style {
+    fn ansi(&self) -> String {
+        let mut s = ANSI_RESET.to_string();
+        if self.bold {
+            s.push_str(ANSI_BOLD);
+        }
+        if self.italic {
+            s.push_str(ANSI_ITALIC);
+        }
+        s
+    }
+}
+
+/// Renders inline markup: `*emphasis*` as bold, `` `code` `` as italic, escapes and links as plain text.
+fn render_inline(text: &str) -> String {
+    let mut out = String::new();
+    let mut style = Style::default();
+    let chars: Vec<char> = text.chars().collect();
+    let mut i = 0;
+
+    while i < chars.len() {
+        match chars[i] {
+            '\\' if i + 1 < chars.len() => {
+                out.push(chars[i + 1]);
+                i += 1;
+            }
+            '*' => {
+                // `**strong**` renders like `*emphasis*`.
+                if chars.get(i + 1) == Some(&'*') {
+                    i += 1;
+                }
+                style.bold = !style.bold;
+                out.push_str(&style.ansi());
+            }
+            '`' => {
+                style.italic = !style.italic;
+                out.push_str(&style.ansi());
+            }
+            '[' => {
+                let rest: String = chars[i + 1..].iter().collect();
+                let link = rest.find("](").and_then(|text_end| {
+                    let url_end = rest[text_end..].find(')')?;
+                    Some((text_end, text_end + url_end))
+                });
+
+                match link {
+                    Some((text_end, url_end)) => {
+                        out.push_str(&rest[..text_end]);
+                        i += rest[..=url_end].chars().count();
+                    }
+                    None => out.push('['),
+                }
+            }
+            c => out.push(c),
+        }
+        i += 1;
+    }
+
+    if style.bold || style.italic {
+        out.push_str(ANSI_RESET);
+    }
+
+    out
+}
+
+/// Number of characters a string occupies in the terminal, ignoring ANSI escape sequences.
+fn visible_width(s: &str) -> usize {
+    let mut width = 0;
+    let mut in_escape = false;
+
+    for c in s.chars() {
+        if in_escape {
+            in_escape = c != 'm';
+        } else if c == '\x1b' {
+            in_escape = true;
+        } else {
+            width += 1;
+        }
+    }
+
+    width
+}
+
+/// Wraps rendered text at `width`, prefixing the first line with `first_indent` and the others with `indent`.
+fn wrap(text: &str, width: usize, first_indent: &str, indent: &str) -> Vec<String> {
+    let mut lines = vec![];
+    let mut line = first_indent.to_string();
+    let mut line_width = visible_width(first_indent);
+    let mut is_empty = true;
+
+    for word in text.split_whitespace() {
+        let word_width = visible_width(word);
+
+        if !is_empty && line_width + 1 + word_width > width {
+            lines.push(line);
+            line = indent.to_string();
+            line_width = visible_width(indent);
+            is_empty = true;
+        }
+
+        if !is_empty {
+            line.push(' ');
+            line_width += 1;
+        }
+
+        line.push_str(word);
+        line_width += word_width;
+        is_empty = false;
+    }
+
+    lines.push(line);
+    lines
+}
+
+fn is_setext_underline(line: &str) -> bool {
+    let line = line.trim();
+    line.len() >= 3 && (line.chars().all(|c| c == '-') || line.chars().all(|c| c == '='))
+}
+
+fn list_item(line: &str) -> Option<&str> {
+    let line = line.trim_start();
+    line.strip_prefix("* ")
+        .or_else(|| line.strip_prefix("- "))
+        .or_else(|| line.strip_prefix("+ "))
+}
+
+/// Renders Markdown for the terminal, wrapping paragraphs at `width` characters.
+pub fn render(markdown: &str, width: usize) -> String {
+    let mut out: Vec<String> = vec![];
+    let mut paragraph: Vec<&str> = vec![];
+    let mut in_code_block = false;
+    let lines: Vec<&str> = markdown.lines().collect();
+
+    let flush = |paragraph: &mut Vec<&str>, out: &mut Vec<String>| {
+        if paragraph.is_empty() {
+            return;
+        }
+        let text = render_inline(&paragraph.join(" "));
+        out.extend(wrap(&text, width, "", ""));
+        out.push(String::new());
+        paragraph.clear();
+    };
+
+    let mut i = 0;
+    while i < lines.len() {
+        let line = lines[i];
+
+        if line.trim_start().starts_with(CODE_FENCE) {
+            flush(&mut paragraph, &mut out);
+            if in_code_block {
+                out.push(String::new());
+            }
+            in_code_block = !in_code_block;
+        } else if in_code_block {
+            out.push(format!("{CODE_INDENT}{line}"));
+        } else if line.trim().is_empty() {
+            flush(&mut paragraph, &mut out);
+        } else if let Some(heading) = line.trim_start().strip_prefix('#') {
+            flush(&mut paragraph, &mut out);
+            let heading = render_inline(heading.trim_start_matches('#').trim());
+            out.push(format!("{ANSI_BOLD}{heading}{ANSI_RESET}"));
+            out.push(String::new());
+        } else if paragraph.is_empty() && lines.get(i + 1).is_some_and(|l| is_setext_underline(l)) {
+            let heading = render_inline(line.trim());
+            out.push(format!("{ANSI_BOLD}{heading}{ANSI_RESET}"));
+            out.push(String::new());
+            // skip the underline.
+            i += 1;
+        } else if let Some(item) = list_item(line) {
+            flush(&mut paragraph, &mut out);
+            out.extend(wrap(&render_inline(item), width, "  • ", "    "));
+            if lines.get(i + 1).is_none_or(|l| list_item(l).is_none()) {
+                out.push(String::new());
+            }
+        } else {
+            paragraph.push(line.trim());
+        }
+
+        i += 1;
+    }
+
+    flush(&mut paragraph, &mut out);
+
+    while out.last().is_some_and(String::is_empty) {
+        out.pop();
+    }
+
+    out.join("\n")
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{render, render_inline, select_part, visible_width, wrap};
+    use crate::template::{ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+
+    const PUZZLE: &str = "\\--- Day 6: Guard Gallivant ---\n----------\n\nThe map shows the *current position* of the guard.\n\n```\n....#.....\n....^....#\n```\n\nThe guard visits `*41*` positions.\n\n\\--- Part Two ---\n----------\n\n* first\n* second\n\nSee [the map](/2024/day/6).\n";
+
+    fn plain(s: &str) -> String {
+        s.replace(ANSI_BOLD, "")
+            .replace(ANSI_ITALIC, "")
+            .replace(ANSI_RESET, "")
+    }
+
+    #[test]
+    fn renders_inline_markup() {
+        assert_eq!(
+            plain(&render_inline("a \\*b\\* [c](/d) `*e*`")),
+            "a *b* c e"
+        );
+        assert_eq!(
+            render_inline("*x*"),
+            format!("{ANSI_RESET}{ANSI_BOLD}x{ANSI_RESET}")
+        );
+    }
+
+    #[test]
+    fn wraps_by_visible_width() {
+        let text = render_inline("aaa *bbb* ccc");
+        assert_eq!(visible_width(&text), 11);
+        let lines = wrap(&text, 8, "", "");
+        assert_eq!(lines.len(), 2);
+        assert_eq!(plain(&lines[0]), "aaa bbb");
+    }
+
+    #[test]
+    fn renders_blocks() {
+        let rendered = plain(&render(PUZZLE, 80));
+        let expected = [
+            "--- Day 6: Guard Gallivant ---",
+            "",
+            "The map shows the current position of the guard.",
+            "",
+            "    ....#.....",
+            "    ....^....#",
+            "",
+            "The guard visits 41 positions.",
+            "",
+            "--- Part Two ---",
+            "",
+            "  • first",
+            "  • second",
+            "",
+            "See the map.",
+        ]
+        .join("\n");
+        assert_eq!(rendered, expected);
+    }
+
+    #[test]
+    fn selects_parts() {
+        let one = select_part(PUZZLE, 1).unwrap();
+        assert_eq!(one.contains("Part Two"), false);
+        let two = select_part(PUZZLE, 2).unwrap();
+        assert_eq!(two.starts_with("\\--- Part Two ---"), true);
+        assert_eq!(select_part("\\--- Day 1 ---\n", 2), None);
+    }
+}
@@ -1,14 +1,27 @@
 use std::{env, fs};
 
 pub mod aoc_cli;
+pub mod benchmark_report;
 pub mod commands;
+pub mod params;
 pub mod runner;
 
 pub use day::*;
+pub use day_set::*;
+pub use params::ParamSet;
+pub use solution::*;
 
 mod day;
+mod day_set;
+mod flamegraph;
+mod markdown;
+mod puzzle;
 mod readme_benchmarks;
+mod readme_progress;
 mod run_multi;
+mod solution;
+mod source_hash;
+mod submission;
 mod timings;
 
 pub const ANSI_ITALIC: &str = "\x1b[3m";
@@ -24,6 +37,13 @@ pub fn read_file(folder: &str, day: Day) -> String {
     f.expect("could not open input file")
 }
 
+/// Helper function that reads a text file to a string together with the parameters for it,
+/// the example overrides for `examples` and the real values otherwise.
+#[must_use]
+pub fn read_file_with_params<P: ParamSet>(folder: &str, day: Day) -> (String, P) {
+    (read_file(folder, day), P::for_folder(folder))
+}
+
 /// Helper function that reads a text file to string, appending a part suffix. E.g. like `01-2.txt`.
 #[must_use]
 pub fn read_file_part(folder: &str, day: Day, part: u8) -> String {
@@ -39,30 +59,107 @@ pub fn read_file_part(folder: &str, day: Day, part: u8) -> String {
 /// Creates the constant `DAY` and sets up the input and runner for each part.
 ///
 /// The optional, second parameter (1 or 2) allows you to only run a single part of the solution.
+///
+/// Alternatively, named arguments configure the solution:
+///  - `parse = parse_input` runs `pub fn parse_input(&str) -> Parsed` once and passes `&Parsed` to both parts.
+///    Parsing is timed separately from the parts.
+///  - `part_one = [...]` and `part_two = [...]` register several named implementations per part, e.g.
+///    `solution!(6, part_two = [part_two, part_two_naive])`. The first one is used by default,
+///    passing `--variants` runs all of them and compares their answers and timings.
+///  - `params = Params` passes the parameters declared with [`params!`](crate::params) for the real input
+///    as `&Params`, a last argument of both parts.
+///  - `debug = print_debug` calls `fn print_debug` once after both parts, outside of the timings, with the
+///    same arguments as the parts. Use it to print intermediate results, e.g. behind a parameter.
+///
+/// Instead of free functions, a type implementing [`Solution`] can be passed, e.g. `solution!(11, Day11)`.
 #[macro_export]
 macro_rules! solution {
     ($day:expr) => {
-        $crate::solution!(@impl $day, [part_one, 1] [part_two, 2]);
+        $crate::solution!(@impl $day, [], [], [], [[part_one], 1] [[part_two], 2]);
     };
     ($day:expr, 1) => {
-        $crate::solution!(@impl $day, [part_one, 1]);
+        $crate::solution!(@impl $day, [], [], [], [[part_one], 1]);
     };
     ($day:expr, 2) => {
-        $crate::solution!(@impl $day, [part_two, 2]);
+        $crate::solution!(@impl $day, [], [], [], [[part_two], 2]);
+    };
+    ($day:expr, $($key:ident = $value:tt),+ $(,)?) => {
+        $crate::solution!(@named $day, [] [] [] [part_one] [part_two], $($key = $value,)+);
+    };
+    ($day:expr, $solution:ident) => {
+        $crate::solution!(@common $day);
+
+        fn main() {
+            let input = $crate::template::read_file("inputs", DAY);
+            $crate::template::runner::run_solution::<$solution>(&input, DAY);
+        }
+    };
+
+    (@named $day:expr, [$($parse:ident)?] [$($params:ident)?] [$($debug:ident)?] [$($one:ident),+] [$($two:ident),+], parse = $new_parse:ident, $($rest:tt)*) => {
+        $crate::solution!(@named $day, [$new_parse] [$($params)?] [$($debug)?] [$($one),+] [$($two),+], $($rest)*);
+    };
+    (@named $day:expr, [$($parse:ident)?] [$($params:ident)?] [$($debug:ident)?] [$($one:ident),+] [$($two:ident),+], params = $new_params:ident, $($rest:tt)*) => {
+        $crate::solution!(@named $day, [$($parse)?] [$new_params] [$($debug)?] [$($one),+] [$($two),+], $($rest)*);
+    };
+    (@named $day:expr, [$($parse:ident)?] [$($params:ident)?] [$($debug:ident)?] [$($one:ident),+] [$($two:ident),+], debug = $new_debug:ident, $($rest:tt)*) => {
+        $crate::solution!(@named $day, [$($parse)?] [$($params)?] [$new_debug] [$($one),+] [$($two),+], $($rest)*);
+    };
+    (@named $day:expr, [$($parse:ident)?] [$($params:ident)?] [$($debug:ident)?] [$($one:ident),+] [$($two:ident),+], part_one = [$($new_one:ident),+ $(,)?], $($rest:tt)*) => {
+        $crate::solution!(@named $day, [$($parse)?] [$($params)?] [$($debug)?] [$($new_one),+] [$($two),+], $($rest)*);
+    };
+    (@named $day:expr, [$($parse:ident)?] [$($params:ident)?] [$($debug:ident)?] [$($one:ident),+] [$($two:ident),+], part_two = [$($new_two:ident),+ $(,)?], $($rest:tt)*) => {
+        $crate::solution!(@named $day, [$($parse)?] [$($params)?] [$($debug)?] [$($one),+] [$($new_two),+], $($rest)*);
+    };
+    (@named $day:expr, [$($parse:ident)?] [$($params:ident)?] [$($debug:ident)?] [$($one:ident),+] [$($two:ident),+], ) => {
+        $crate::solution!(@impl $day, [$($parse)?], [$($params)?], [$($debug)?], [[$($one),+], 1] [[$($two),+], 2]);
     };
 
-    (@impl $day:expr, $( [$func:expr, $part:expr] )*) => {
+    (@common $day:expr) => {
         /// The current day.
         const DAY: $crate::template::Day = $crate::day!($day);
 
         #[cfg(feature = "dhat-heap")]
         #[global_allocator]
         static ALLOC: dhat::Alloc = dhat::Alloc;
+    };
+
+    // `input` and `params` are passed in, so they name the bindings of the `@impl` expansion.
+    (@params $params:ident, []) => {};
+    (@params $params:ident, [$ty:ident]) => {
+        let $params = <$ty as $crate::template::ParamSet>::real();
+    };
+    (@call $func:ident, $input:ident, $params:ident, []) => {
+        $func($input)
+    };
+    (@call $func:ident, $input:ident, $params:ident, [$ty:ident]) => {
+        $func($input, &$params)
+    };
+
+    (@impl $day:expr, [$($parse:ident)?], $params:tt, [$($debug:ident)?], $( [[$func:ident $(, $variant:ident)*], $part:expr] )*) => {
+        $crate::solution!(@common $day);
 
         fn main() {
             use $crate::template::runner::*;
             let input = $crate::template::read_file("inputs", DAY);
-            $( run_part($func, &input, DAY, $part); )*
+            let input = input.as_str();
+            $(
+                let parsed = run_parse($parse, input);
+                let input = &parsed;
+            )?
+            $crate::solution!(@params params, $params);
+            let compare_variants = std::env::args().any(|x| x == "--variants");
+            $(
+                if compare_variants {
+                    let variants: &[Variant<_, _>] = &[
+                        (stringify!($func), &|input| $crate::solution!(@call $func, input, params, $params)),
+                        $( (stringify!($variant), &|input| $crate::solution!(@call $variant, input, params, $params)), )*
+                    ];
+                    run_variants(variants, input, DAY, $part);
+                } else {
+                    run_part(|input| $crate::solution!(@call $func, input, params, $params), input, DAY, $part);
+                }
+            )*
+            $( $crate::solution!(@call $debug, input, params, $params); )?
         }
     };
 }
@@ -0,0 +1,166 @@
+/// Module that resolves the named parameters declared with the [`params!`](crate::params) macro.
+///
+/// Parameters are values, chosen once per input source and passed to the solution by reference:
+///  - for the real input, a parameter resolves to the value passed on the command line as
+///    `--param <name>=<value>`, or its real default.
+///  - for an example input, it resolves to its example override, or its real default.
+use std::{
+    collections::HashMap,
+    env,
+    str::FromStr,
+    sync::{Once, OnceLock},
+};
+
+static CLI_OVERRIDES: OnceLock<HashMap<String, String>> = OnceLock::new();
+static CHECK_NAMES: Once = Once::new();
+
+/// Parses `--param <name>=<value>` pairs from a list of command-line arguments.
+fn parse_cli_overrides(args: &[String]) -> HashMap<String, String> {
+    args.windows(2)
+        .filter(|pair| pair[0] == "--param")
+        .filter_map(|pair| {
+            let (name, value) = pair[1].split_once('=')?;
+            Some((name.trim().to_string(), value.trim().to_string()))
+        })
+        .collect()
+}
+
+fn cli_overrides() -> &'static HashMap<String, String> {
+    CLI_OVERRIDES.get_or_init(|| parse_cli_overrides(&env::args().collect::<Vec<_>>()))
+}
+
+// Not part of the public API
+#[doc(hidden)]
+pub fn __check_names(names: &[&str]) {
+    CHECK_NAMES.call_once(|| {
+        for name in cli_overrides().keys() {
+            if !names.contains(&name.as_str()) {
+                eprintln!("Warning: unknown parameter `{name}`, expecting one of {names:?}.");
+            }
+        }
+    });
+}
+
+// Not part of the public API
+#[doc(hidden)]
+pub fn __resolve<T: FromStr>(name: &str, default: T) -> T {
+    match cli_overrides().get(name) {
+        Some(value) => value
+            .parse()
+            .unwrap_or_else(|_| panic!("invalid value `{value}` for parameter `{name}`")),
+        None => default,
+    }
+}
+
+/// A set of parameters, implemented by the `Params` struct generated by [`params!`](crate::params).
+/// `()` is the empty set, for solutions without parameters.
+pub trait ParamSet: Sized {
+    /// The values for the real input, with command-line overrides applied.
+    fn real() -> Self;
+
+    /// The values for the example input.
+    fn example() -> Self;
+
+    /// The values for an input read from `data/<folder>`.
+    fn for_folder(folder: &str) -> Self {
+        if folder == "examples" {
+            Self::example()
+        } else {
+            Self::real()
+        }
+    }
+}
+
+impl ParamSet for () {
+    fn real() -> Self {}
+
+    fn example() -> Self {}
+}
+
+/// Declares named parameters of a solution with a real default and an optional example override.
+///
+/// Generates a `Params` struct implementing [`ParamSet`]. Register it with `solution!(<day>, params = Params)`
+/// to receive the parameters for the input as a last argument of both parts, see
+/// [`template::params`](crate::template::params) for how values are resolved.
+///
+/// ```
+/// advent_of_code::params! {
+///     /// Width of the grid.
+///     width: i32 = 101, example = 11;
+///     /// Number of steps to simulate.
+///     steps: u32 = 100;
+/// }
+/// ```
+#[macro_export]
+macro_rules! params {
+    ($( $(#[$meta:meta])* $name:ident: $ty:ty = $real:expr $(, example = $example:expr)? );+ $(;)?) => {
+        /// Parameters of the solution.
+        #[derive(Debug, Clone)]
+        pub struct Params {
+            $( $(#[$meta])* pub $name: $ty, )+
+        }
+
+        impl $crate::template::params::ParamSet for Params {
+            fn real() -> Self {
+                $crate::template::params::__check_names(&[$(stringify!($name)),+]);
+
+                Self {
+                    $(
+                        $name: $crate::template::params::__resolve::<$ty>(
+                            stringify!($name),
+                            $real,
+                        ),
+                    )+
+                }
+            }
+
+            fn example() -> Self {
+                Self {
+                    $( $name: $crate::params!(@example $real $(, $example)?), )+
+                }
+            }
+        }
+    };
+
+    (@example $real:expr) => {
+        $real
+    };
+    (@example $real:expr, $example:expr) => {
+        $example
+    };
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{__resolve, parse_cli_overrides, ParamSet};
+
+    #[test]
+    fn parses_cli_overrides() {
+        let args: Vec<String> = ["11", "--time", "--param", "blinks=6", "--param", "size = 7"]
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+        let overrides = parse_cli_overrides(&args);
+        assert_eq!(overrides.len(), 2);
+        assert_eq!(overrides.get("blinks"), Some(&"6".to_string()));
+        assert_eq!(overrides.get("size"), Some(&"7".to_string()));
+    }
+
+    #[test]
+    fn resolves_defaults() {
+        assert_eq!(__resolve::<u32>("__test_steps", 100), 100);
+    }
+
+    crate::params! {
+        __test_width: i32 = 101, example = 11;
+        __test_steps: u32 = 100;
+    }
+
+    #[test]
+    fn selects_params_by_folder() {
+        let real = Params::for_folder("inputs");
+        assert_eq!((real.__test_width, real.__test_steps), (101, 100));
+        let example = Params::for_folder("examples");
+        assert_eq!((example.__test_width, example.__test_steps), (11, 100));
+    }
+}
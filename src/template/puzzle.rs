@@ -0,0 +1,188 @@
+/// Module that extracts information from puzzle descriptions downloaded by aoc-cli.
+use std::fs;
+
+use crate::template::markdown::{CODE_FENCE, PART_TWO_HEADING};
+use crate::template::Day;
+
+static ANSWER_PREFIX: &str = "Your puzzle answer was";
+
+/// A puzzle description as stored in `data/puzzles/NN.md`.
+#[derive(Clone, Debug)]
+pub struct Puzzle {
+    pub day: Day,
+    pub title: Option<String>,
+    pub answers: Vec<String>,
+}
+
+impl Puzzle {
+    /// Parses the Markdown of a puzzle description.
+    pub fn parse(day: Day, markdown: &str) -> Self {
+        Self {
+            day,
+            title: markdown.lines().find_map(parse_title),
+            answers: markdown.lines().filter_map(parse_answer).collect(),
+        }
+    }
+
+    /// Reads the puzzle description for a day from disk. Returns `None` if it has not been downloaded.
+    pub fn read_from_file(day: Day) -> Option<Self> {
+        read_markdown(day).map(|markdown| Self::parse(day, &markdown))
+    }
+
+    /// Number of stars collected for this puzzle, derived from the accepted answers.
+    pub fn stars(&self) -> usize {
+        self.answers.len().min(2)
+    }
+}
+
+#[must_use]
+pub fn get_path_for_puzzle(day: Day) -> String {
+    format!("./data/puzzles/{day}.md")
+}
+
+/// Reads the Markdown of a downloaded puzzle description.
+pub fn read_markdown(day: Day) -> Option<String> {
+    fs::read_to_string(get_path_for_puzzle(day)).ok()
+}
+
+/// Extracts the contents of all fenced code blocks, each ending with a newline.
+pub fn code_blocks(markdown: &str) -> Vec<String> {
+    let mut blocks = vec![];
+    let mut current: Option<String> = None;
+
+    for line in markdown.lines() {
+        if line.trim_start().starts_with(CODE_FENCE) {
+            match current.take() {
+                Some(block) => blocks.push(block),
+                None => current = Some(String::new()),
+            }
+        } else if let Some(block) = current.as_mut() {
+            block.push_str(line);
+            block.push('\n');
+        }
+    }
+
+    blocks
+}
+
+/// Finds the last emphasized inline code span of a section, which is usually the answer for the example.
+/// Emphasis may be inside the span like `` `*42*` `` or around it like ``*`42`*``.
+fn last_emphasized_code(section: &str) -> Option<String> {
+    let mut answer = None;
+
+    for line in section.lines() {
+        if line.trim_start().starts_with(CODE_FENCE) {
+            continue;
+        }
+
+        let bytes = line.as_bytes();
+        let mut rest_start = 0;
+
+        while let Some(start) = line[rest_start..].find('`').map(|i| rest_start + i) {
+            let Some(end) = line[start + 1..].find('`').map(|i| start + 1 + i) else {
+                break;
+            };
+
+            let content = &line[start + 1..end];
+            let is_wrapped =
+                start > 0 && bytes[start - 1] == b'*' && bytes.get(end + 1) == Some(&b'*');
+            let value = if is_wrapped {
+                Some(content)
+            } else {
+                content.strip_prefix('*').and_then(|c| c.strip_suffix('*'))
+            };
+
+            if let Some(value) = value.map(str::trim).filter(|v| !v.is_empty()) {
+                answer = Some(value.to_string());
+            }
+
+            rest_start = end + 1;
+        }
+    }
+
+    answer
+}
+
+/// Proposes the expected example answers of both parts, taken from the last emphasized code span of each part.
+pub fn proposed_answers(markdown: &str) -> [Option<String>; 2] {
+    match markdown.split_once(PART_TWO_HEADING) {
+        Some((one, two)) => [last_emphasized_code(one), last_emphasized_code(two)],
+        None => [last_emphasized_code(markdown), None],
+    }
+}
+
+/// Extracts the title from a heading like `\--- Day 1: Historian Hysteria ---`.
+fn parse_title(line: &str) -> Option<String> {
+    let (_, rest) = line.split_once("--- Day ")?;
+    let (_, title) = rest.split_once(':')?;
+    let title = title.trim().trim_end_matches('-').trim();
+
+    if title.is_empty() {
+        None
+    } else {
+        Some(title.to_string())
+    }
+}
+
+/// Extracts the answer from a line like ``Your puzzle answer was `42`.``.
+fn parse_answer(line: &str) -> Option<String> {
+    let (_, rest) = line.split_once(ANSWER_PREFIX)?;
+    let answer = rest
+        .trim()
+        .trim_end_matches('.')
+        .trim_matches(|c| c == '`' || c == '*' || c == ' ');
+
+    Some(answer.to_string())
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{code_blocks, proposed_answers, Puzzle};
+    use crate::day;
+
+    const SOLVED: &str = "\\--- Day 1: Historian Hysteria ---\n----------\n\nSome text.\n\nYour puzzle answer was `2086478`.\n\n\\--- Part Two ---\n----------\n\nMore text.\n\nYour puzzle answer was `24941624`.\n\nBoth parts of this puzzle are complete! They provide two gold stars: \\*\\*\n";
+
+    #[test]
+    fn parses_title() {
+        let puzzle = Puzzle::parse(day!(1), SOLVED);
+        assert_eq!(puzzle.title, Some("Historian Hysteria".to_string()));
+    }
+
+    #[test]
+    fn parses_answers() {
+        let puzzle = Puzzle::parse(day!(1), SOLVED);
+        assert_eq!(puzzle.answers, vec!["2086478", "24941624"]);
+        assert_eq!(puzzle.stars(), 2);
+    }
+
+    #[test]
+    fn handles_unsolved_puzzles() {
+        let puzzle = Puzzle::parse(day!(3), "\\--- Day 3: Mull It Over ---\n\nSome text.\n");
+        assert_eq!(puzzle.title, Some("Mull It Over".to_string()));
+        assert_eq!(puzzle.stars(), 0);
+    }
+
+    const EXAMPLES: &str = "\\--- Day 6: Guard Gallivant ---\n----------\n\nFor example:\n\n```\n....#.....\n.........#\n```\n\nThe guard moves like this:\n\n```\n....#.....\n....^....#\n```\n\nIn this example, the guard will visit `*41*` distinct positions on your map.\n\n\\--- Part Two ---\n----------\n\nThere are `6` candidates, only *`6`* of them work.\n";
+
+    #[test]
+    fn extracts_code_blocks() {
+        let blocks = code_blocks(EXAMPLES);
+        assert_eq!(blocks.len(), 2);
+        assert_eq!(blocks[0], "....#.....\n.........#\n");
+    }
+
+    #[test]
+    fn proposes_answers() {
+        assert_eq!(
+            proposed_answers(EXAMPLES),
+            [Some("41".to_string()), Some("6".to_string())]
+        );
+        assert_eq!(proposed_answers("no answers here"), [None, None]);
+    }
+
+    #[test]
+    fn handles_missing_title() {
+        let puzzle = Puzzle::parse(day!(3), "Some text.\n");
+        assert_eq!(puzzle.title, None);
+    }
+}
@@ -21,8 +21,8 @@ impl From<std::io::Error> for Error {
 }
 
 pub struct TablePosition {
-    pos_start: usize,
-    pos_end: usize,
+    pub(super) pos_start: usize,
+    pub(super) pos_end: usize,
 }
 
 #[must_use]
@@ -30,8 +30,8 @@ pub fn get_path_for_bin(day: Day) -> String {
     format!("./src/bin/{day}.rs")
 }
 
-fn locate_table(readme: &str) -> Result<TablePosition, Error> {
-    let matches: Vec<_> = readme.match_indices(MARKER).collect();
+pub(super) fn locate_table(readme: &str, marker: &str) -> Result<TablePosition, Error> {
+    let matches: Vec<_> = readme.match_indices(marker).collect();
 
     if matches.len() > 2 {
         return Err(Error::Parser(
@@ -59,16 +59,17 @@ fn construct_table(prefix: &str, timings: Timings, total_millis: f64) -> String
         MARKER.into(),
         header,
         String::new(),
-        "| Day | Part 1 | Part 2 |".into(),
-        "| :---: | :---: | :---:  |".into(),
+        "| Day | Parse | Part 1 | Part 2 |".into(),
+        "| :---: | :---: | :---: | :---:  |".into(),
     ];
 
     for timing in timings.data {
         let path = get_path_for_bin(timing.day);
         lines.push(format!(
-            "| [Day {}]({}) | `{}` | `{}` |",
+            "| [Day {}]({}) | `{}` | `{}` | `{}` |",
             timing.day.into_inner(),
             path,
+            timing.parse.unwrap_or_else(|| "-".into()),
             timing.part_1.unwrap_or_else(|| "-".into()),
             timing.part_2.unwrap_or_else(|| "-".into())
         ));
@@ -82,7 +83,7 @@ fn construct_table(prefix: &str, timings: Timings, total_millis: f64) -> String
 }
 
 fn update_content(s: &mut String, timings: Timings, total_millis: f64) -> Result<(), Error> {
-    let positions = locate_table(s)?;
+    let positions = locate_table(s, MARKER)?;
     let table = construct_table("##", timings, total_millis);
     s.replace_range(positions.pos_start..positions.pos_end, &table);
     Ok(())
@@ -107,21 +108,27 @@ mod tests {
             data: vec![
                 Timing {
                     day: day!(1),
+                    parse: None,
                     part_1: Some("10ms".into()),
                     part_2: Some("20ms".into()),
                     total_nanos: 3e+10,
+                    hash: None,
                 },
                 Timing {
                     day: day!(2),
+                    parse: Some("5ms".into()),
                     part_1: Some("30ms".into()),
                     part_2: Some("40ms".into()),
                     total_nanos: 7e+10,
+                    hash: None,
                 },
                 Timing {
                     day: day!(4),
+                    parse: None,
                     part_1: Some("40ms".into()),
                     part_2: Some("50ms".into()),
                     total_nanos: 9e+10,
+                    hash: None,
                 },
             ],
         }
@@ -167,11 +174,11 @@ mod tests {
             "<!--- benchmarking table --->",
             "## Benchmarks",
             "",
-            "| Day | Part 1 | Part 2 |",
-            "| :---: | :---: | :---:  |",
-            "| [Day 1](./src/bin/01.rs) | `10ms` | `20ms` |",
-            "| [Day 2](./src/bin/02.rs) | `30ms` | `40ms` |",
-            "| [Day 4](./src/bin/04.rs) | `40ms` | `50ms` |",
+            "| Day | Parse | Part 1 | Part 2 |",
+            "| :---: | :---: | :---: | :---:  |",
+            "| [Day 1](./src/bin/01.rs) | `-` | `10ms` | `20ms` |",
+            "| [Day 2](./src/bin/02.rs) | `5ms` | `30ms` | `40ms` |",
+            "| [Day 4](./src/bin/04.rs) | `-` | `40ms` | `50ms` |",
             "",
             "**Total: 190.00ms**",
             "<!--- benchmarking table --->",
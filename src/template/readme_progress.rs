@@ -0,0 +1,174 @@
+/// Module that updates the readme with puzzle progress and the configured year in its heading.
+/// Progress is derived from the puzzle descriptions in `data/puzzles`, which list accepted answers once downloaded.
+use std::fs;
+
+use crate::template::puzzle::Puzzle;
+use crate::template::readme_benchmarks::{locate_table, Error};
+use crate::template::{all_days, aoc_cli};
+
+static MARKER: &str = "<!--- advent_readme_stars table --->";
+static HEADING_MARKER: &str = "<!--- advent_readme heading --->";
+
+fn construct_table(year: u16, puzzles: &[Puzzle]) -> String {
+    let mut lines: Vec<String> = vec![
+        MARKER.into(),
+        format!("## {year} Results"),
+        String::new(),
+        "| Day | Part 1 | Part 2 |".into(),
+        "| :---: | :---: | :---: |".into(),
+    ];
+
+    for puzzle in puzzles {
+        let day = puzzle.day.into_inner();
+        let label = match &puzzle.title {
+            Some(title) => format!("Day {day}: {title}"),
+            None => format!("Day {day}"),
+        };
+        let stars = puzzle.stars();
+
+        lines.push(format!(
+            "| [{label}](https://adventofcode.com/{year}/day/{day}) | {} | {} |",
+            if stars >= 1 { "⭐" } else { " " },
+            if stars >= 2 { "⭐" } else { " " },
+        ));
+    }
+
+    let total: usize = puzzles.iter().map(Puzzle::stars).sum();
+
+    lines.push(String::new());
+    lines.push(format!("**Total: {total} ⭐**"));
+    lines.push(MARKER.into());
+
+    lines.join("\n")
+}
+
+fn construct_heading(year: u16) -> String {
+    [
+        HEADING_MARKER.into(),
+        format!("# 🎄 Advent of Code {year}"),
+        HEADING_MARKER.into(),
+    ]
+    .join("\n")
+}
+
+/// Renders the heading between its markers, readmes without them keep their heading.
+fn update_heading(s: &mut String, year: u16) -> Result<(), Error> {
+    if !s.contains(HEADING_MARKER) {
+        return Ok(());
+    }
+
+    let positions = locate_table(s, HEADING_MARKER)?;
+    s.replace_range(
+        positions.pos_start..positions.pos_end,
+        &construct_heading(year),
+    );
+    Ok(())
+}
+
+fn update_content(s: &mut String, year: u16, puzzles: &[Puzzle]) -> Result<(), Error> {
+    update_heading(s, year)?;
+    let positions = locate_table(s, MARKER)?;
+    let table = construct_table(year, puzzles);
+    s.replace_range(positions.pos_start..positions.pos_end, &table);
+    Ok(())
+}
+
+pub fn update() -> Result<(), Error> {
+    let year = aoc_cli::get_year()
+        .ok_or_else(|| Error::Parser("AOC_YEAR is not set or not a valid year.".into()))?;
+    let puzzles: Vec<Puzzle> = all_days().filter_map(Puzzle::read_from_file).collect();
+
+    let path = "README.md";
+    let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
+    update_content(&mut readme, year, &puzzles)?;
+    fs::write(path, &readme)?;
+    Ok(())
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{update_content, HEADING_MARKER, MARKER};
+    use crate::{day, template::puzzle::Puzzle};
+
+    fn get_mock_puzzles() -> Vec<Puzzle> {
+        vec![
+            Puzzle {
+                day: day!(1),
+                title: Some("Historian Hysteria".into()),
+                answers: vec!["1".into(), "2".into()],
+            },
+            Puzzle {
+                day: day!(2),
+                title: None,
+                answers: vec!["3".into()],
+            },
+        ]
+    }
+
+    #[test]
+    #[should_panic]
+    fn errors_if_marker_not_present() {
+        let mut s = "# readme".to_string();
+        update_content(&mut s, 2024, &get_mock_puzzles()).unwrap();
+    }
+
+    #[test]
+    fn renders_heading_between_markers() {
+        let mut s = format!(
+            "{}\n# Advent of Code {{year}}\n{}\n```sh\n# {{year}}\n```\n{}{}",
+            HEADING_MARKER, HEADING_MARKER, MARKER, MARKER
+        );
+        update_content(&mut s, 2024, &get_mock_puzzles()).unwrap();
+        let heading = format!(
+            "{}\n# 🎄 Advent of Code 2024\n{}\n```sh\n# {{year}}\n```\n",
+            HEADING_MARKER, HEADING_MARKER
+        );
+        assert_eq!(s.starts_with(&heading), true);
+
+        // the year is part of the rendered region, so changing it re-renders the heading.
+        update_content(&mut s, 2023, &get_mock_puzzles()).unwrap();
+        assert_eq!(s.contains("# 🎄 Advent of Code 2023\n"), true);
+        assert_eq!(s.contains("2024\n"), false);
+    }
+
+    #[test]
+    fn keeps_heading_without_markers() {
+        let mut s = format!("# Advent of Code {{year}}\n{}{}", MARKER, MARKER);
+        update_content(&mut s, 2024, &get_mock_puzzles()).unwrap();
+        assert_eq!(s.starts_with("# Advent of Code {year}\n"), true);
+    }
+
+    #[test]
+    fn updates_existing_progress() {
+        let mut s = format!(
+            "{}{}\n{}{}\nbaz",
+            HEADING_MARKER, HEADING_MARKER, MARKER, MARKER
+        );
+        update_content(&mut s, 2024, &get_mock_puzzles()).unwrap();
+        let once = s.clone();
+        update_content(&mut s, 2024, &get_mock_puzzles()).unwrap();
+        assert_eq!(s, once);
+    }
+
+    #[test]
+    fn format_progress() {
+        let mut s = format!("foo\n{}\n{}\nbaz", MARKER, MARKER);
+        update_content(&mut s, 2024, &get_mock_puzzles()).unwrap();
+        let expected = [
+            "foo",
+            "<!--- advent_readme_stars table --->",
+            "## 2024 Results",
+            "",
+            "| Day | Part 1 | Part 2 |",
+            "| :---: | :---: | :---: |",
+            "| [Day 1: Historian Hysteria](https://adventofcode.com/2024/day/1) | ⭐ | ⭐ |",
+            "| [Day 2](https://adventofcode.com/2024/day/2) | ⭐ |   |",
+            "",
+            "**Total: 3 ⭐**",
+            "<!--- advent_readme_stars table --->",
+            "baz",
+        ]
+        .join("\n");
+        assert_eq!(s, expected);
+    }
+}
@@ -1,38 +1,41 @@
-use std::{collections::HashSet, io};
+use std::io;
 
-use crate::template::{Day, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+use crate::template::{Day, DaySet, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
 
-use super::{
-    all_days,
-    timings::{Timing, Timings},
-};
+use super::source_hash::day_hash;
+use super::timings::{Timing, Timings};
 
-pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -> Option<Timings> {
+pub fn run_multi(
+    days_to_run: &DaySet,
+    is_release: bool,
+    is_timed: bool,
+    is_variants: bool,
+) -> Option<Timings> {
     let mut timings: Vec<Timing> = Vec::with_capacity(days_to_run.len());
 
     let mut need_space = false;
 
-    // NOTE: use non-duplicate, sorted day values.
-    all_days()
-        .filter(|day| days_to_run.contains(day))
-        .for_each(|day| {
-            if need_space {
-                println!();
-            }
-            need_space = true;
-
-            println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
-            println!("------");
-
-            let output = child_commands::run_solution(day, is_timed, is_release).unwrap();
-
-            if output.is_empty() {
-                println!("Not solved.");
-            } else {
-                let val = child_commands::parse_exec_time(&output, day);
-                timings.push(val);
-            }
-        });
+    days_to_run.iter().for_each(|day| {
+        if need_space {
+            println!();
+        }
+        need_space = true;
+
+        println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
+        println!("------");
+
+        // hash before running, so edits made while benching are picked up by the next run.
+        let hash = day_hash(day);
+        let output = child_commands::run_solution(day, is_timed, is_release, is_variants).unwrap();
+
+        if output.is_empty() {
+            println!("Not solved.");
+        } else {
+            let mut val = child_commands::parse_exec_time(&output, day);
+            val.hash = Some(hash);
+            timings.push(val);
+        }
+    });
 
     if is_timed {
         let timings = Timings { data: timings };
@@ -77,7 +80,12 @@ pub mod child_commands {
     };
 
     /// Run the solution bin for a given day
-    pub fn run_solution(day: Day, is_timed: bool, is_release: bool) -> Result<Vec<String>, Error> {
+    pub fn run_solution(
+        day: Day,
+        is_timed: bool,
+        is_release: bool,
+        is_variants: bool,
+    ) -> Result<Vec<String>, Error> {
         // skip command invocation for days that have not been scaffolded yet.
         if !Path::new(&get_path_for_bin(day)).exists() {
             return Ok(vec![]);
@@ -90,12 +98,19 @@ pub mod child_commands {
             args.push("--release");
         }
 
+        if is_timed || is_variants {
+            args.push("--");
+        }
+
         if is_timed {
             // mirror `--time` flag to child invocations.
-            args.push("--");
             args.push("--time");
         }
 
+        if is_variants {
+            args.push("--variants");
+        }
+
         // spawn child command with piped stdout/stderr.
         // forward output to stdout/stderr while grabbing stdout lines.
 
@@ -131,9 +146,11 @@ pub mod child_commands {
     pub fn parse_exec_time(output: &[String], day: Day) -> super::Timing {
         let mut timings = super::Timing {
             day,
+            parse: None,
             part_1: None,
             part_2: None,
             total_nanos: 0_f64,
+            hash: None,
         };
 
         output
@@ -152,7 +169,9 @@ pub mod child_commands {
                 Some((part, timing_str, nanos))
             })
             .for_each(|(part, timing_str, nanos)| {
-                if part.contains("Part 1") {
+                if part.contains("Parse") {
+                    timings.parse = Some(timing_str.into());
+                } else if part.contains("Part 1") {
                     timings.part_1 = Some(timing_str.into());
                 } else if part.contains("Part 2") {
                     timings.part_2 = Some(timing_str.into());
@@ -169,24 +188,27 @@ pub mod child_commands {
     }
 
     fn parse_time(line: &str) -> Option<(&str, f64)> {
-        // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
         let str_timing = line
             .split(" samples)")
             .next()?
             .split('(')
-            .last()?
+            .next_back()?
             .split('@')
             .next()?
             .trim();
 
-        let parsed_timing = match str_timing {
+        Some((str_timing, parse_duration(str_timing)?))
+    }
+
+    /// Converts a formatted duration such as `74.13ms` back to nanoseconds.
+    pub fn parse_duration(s: &str) -> Option<f64> {
+        // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
+        match s {
             s if s.contains("ns") => s.split("ns").next()?.parse::<f64>().ok(),
             s if s.contains("µs") => parse_to_float(s, "µs").map(|x| x * 1000_f64),
             s if s.contains("ms") => parse_to_float(s, "ms").map(|x| x * 1_000_000_f64),
             s => parse_to_float(s, "s").map(|x| x * 1_000_000_000_f64),
-        }?;
-
-        Some((str_timing, parsed_timing))
+        }
     }
 
     /// copied from: https://github.com/rust-lang/rust/blob/1.64.0/library/std/src/macros.rs#L328-L333
@@ -224,6 +246,22 @@ pub mod child_commands {
             assert_eq!(res.part_2.unwrap(), "74.13ms");
         }
 
+        #[test]
+        fn parses_parse_times() {
+            let res = parse_exec_time(
+                &[
+                    "Parse: ✔ (1.5ms @ 100 samples)".into(),
+                    "Part 1: 0 (74.13ns @ 100000 samples)".into(),
+                    "Part 2: 10 (74.13ms @ 99999 samples)".into(),
+                    "".into(),
+                ],
+                day!(1),
+            );
+            assert_approx_eq!(res.total_nanos, 75630074.13_f64);
+            assert_eq!(res.parse.unwrap(), "1.5ms");
+            assert_eq!(res.part_1.unwrap(), "74.13ns");
+        }
+
         #[test]
         fn parses_with_patterns_in_input() {
             let res = parse_exec_time(
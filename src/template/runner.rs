@@ -1,27 +1,169 @@
 /// Encapsulates code that interacts with solution functions.
+use std::cell::RefCell;
 use std::fmt::Display;
 use std::hint::black_box;
 use std::io::{stdout, Write};
-use std::process::Output;
 use std::time::{Duration, Instant};
 use std::{cmp, env, process};
 
+#[cfg(feature = "cpu-profile")]
+use crate::template::flamegraph::{self, FoldedStack};
+use crate::template::markdown;
+use crate::template::puzzle::read_markdown;
+use crate::template::submission::Submission;
 use crate::template::ANSI_BOLD;
-use crate::template::{aoc_cli, Day, ANSI_ITALIC, ANSI_RESET};
+use crate::template::{aoc_cli, Day, ParamSet, Solution, ANSI_ITALIC, ANSI_RESET};
 
 pub fn run_part<I: Copy, T: Display>(func: impl Fn(I) -> Option<T>, input: I, day: Day, part: u8) {
     let part_str = format!("Part {part}");
 
     let (result, duration, samples) =
-        run_timed(func, input, |result| print_result(result, &part_str, ""));
+        run_timed(&func, input, |result| print_result(result, &part_str, ""));
 
     print_result(&result, &part_str, &format_duration(&duration, samples));
 
+    if should_profile(part) {
+        profile_cpu(&func, input, &duration, day, part);
+    }
+
     if let Some(result) = result {
         submit_result(result, day, part);
     }
 }
 
+/// A named implementation of a solution part.
+pub type Variant<'a, I, T> = (&'a str, &'a dyn Fn(I) -> Option<T>);
+
+/// Run the parse phase of a solution and return its output, timed like a solution part.
+pub fn run_parse<T>(func: impl Fn(&str) -> T, input: &str) -> T {
+    let (parsed, duration, samples) = run_timed(&func, input, |_| print!("Parse: ✔"));
+
+    print!("\r");
+    println!("Parse: ✔{}", format_duration(&duration, samples));
+
+    parsed
+}
+
+/// Run both parts of a [`Solution`] with the parameters for the real input, sharing the parsed input
+/// and the intermediate results of part one.
+pub fn run_solution<S: Solution>(input: &str, day: Day) {
+    let params = S::Params::real();
+    let parsed = run_parse(S::parse, input);
+    let carry = RefCell::new(S::Carry::default());
+
+    run_part(
+        |parsed: &S::Parsed| {
+            let mut next_carry = S::Carry::default();
+            let result = S::part_one(parsed, &params, &mut next_carry);
+            carry.replace(next_carry);
+            result
+        },
+        &parsed,
+        day,
+        1,
+    );
+
+    let carry = carry.into_inner();
+    run_part(
+        |parsed| S::part_two(parsed, &params, &carry),
+        &parsed,
+        day,
+        2,
+    );
+}
+
+/// Run every registered implementation of a solution part, check that they agree on the answer and
+/// print a comparative timing table. The first variant serves as the baseline for relative timings.
+pub fn run_variants<I: Copy, T: Display>(variants: &[Variant<I, T>], input: I, day: Day, part: u8) {
+    println!(
+        "{ANSI_BOLD}Part {part}{ANSI_RESET} ({} variants)",
+        variants.len()
+    );
+
+    let mut stdout = stdout();
+
+    let results: Vec<VariantResult> = variants
+        .iter()
+        .map(|(name, func)| {
+            print!("{name}");
+            let _ = stdout.flush();
+            let (result, duration, samples) = run_timed(func, input, |_| {});
+            // clear the intermediate progress output.
+            print!("\r{:width$}\r", "", width = name.chars().count() + 12);
+            let _ = stdout.flush();
+
+            VariantResult {
+                name: (*name).to_string(),
+                answer: result.map(|x| x.to_string()),
+                duration,
+                samples,
+            }
+        })
+        .collect();
+
+    for line in format_variant_table(&results) {
+        println!("{line}");
+    }
+
+    if results.windows(2).any(|w| w[0].answer != w[1].answer) {
+        eprintln!("Variants of day {day} part {part} disagree on the answer.");
+        process::exit(1);
+    }
+}
+
+struct VariantResult {
+    name: String,
+    answer: Option<String>,
+    duration: Duration,
+    samples: u128,
+}
+
+fn format_variant_table(results: &[VariantResult]) -> Vec<String> {
+    let Some(baseline) = results.first() else {
+        return vec![];
+    };
+
+    let rows: Vec<[String; 4]> = results
+        .iter()
+        .map(|result| {
+            let relative =
+                baseline.duration.as_secs_f64() / result.duration.as_secs_f64().max(1e-9);
+
+            [
+                result.name.clone(),
+                result.answer.clone().unwrap_or_else(|| "✖".into()),
+                format!("{:.1?} @ {} runs", result.duration, result.samples),
+                format!("{relative:.2}x"),
+            ]
+        })
+        .collect();
+
+    let widths: Vec<usize> = (0..4)
+        .map(|col| {
+            rows.iter()
+                .map(|row| row[col].chars().count())
+                .max()
+                .unwrap_or(0)
+        })
+        .collect();
+
+    rows.iter()
+        .map(|row| {
+            format!(
+                "  {:<w0$}  {:<w1$}  {:>w2$}  {:>w3$}",
+                row[0],
+                row[1],
+                row[2],
+                row[3],
+                w0 = widths[0],
+                w1 = widths[1],
+                w2 = widths[2],
+                w3 = widths[3],
+            )
+        })
+        .collect()
+}
+
 /// Run a solution part. The behavior differs depending on whether we are running a release or debug build:
 ///  1. in debug, the function is executed once.
 ///  2. in release, the function is benched (approx. 1 second of execution time or 10 samples, whatever take longer.)
@@ -74,6 +216,90 @@ fn bench<I: Copy, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (
     )
 }
 
+/// Whether to profile `part`. `--profile-cpu` profiles every part, `--profile-part <n>` narrows it down to one,
+/// so an external sampler can record a separate profile per part.
+fn should_profile(part: u8) -> bool {
+    let args: Vec<String> = env::args().collect();
+
+    args.iter().any(|x| x == "--profile-cpu")
+        && args
+            .windows(2)
+            .find(|pair| pair[0] == "--profile-part")
+            .is_none_or(|pair| pair[1] == part.to_string())
+}
+
+/// Run a solution part repeatedly (approx. 2 seconds of execution time) so a sampling profiler can collect enough samples.
+/// When built with the `cpu-profile` feature, samples are collected in-process and written to `profiles/`.
+fn profile_cpu<I: Copy, T>(
+    func: impl Fn(I) -> T,
+    input: I,
+    base_time: &Duration,
+    day: Day,
+    part: u8,
+) {
+    let mut stdout = stdout();
+
+    print!(" > {ANSI_ITALIC}profiling{ANSI_RESET}");
+    let _ = stdout.flush();
+
+    let iterations = (Duration::from_secs(2).as_nanos() / cmp::max(base_time.as_nanos(), 10))
+        .clamp(10, 1_000_000);
+
+    #[cfg(feature = "cpu-profile")]
+    let guard = pprof::ProfilerGuardBuilder::default()
+        .frequency(997)
+        .blocklist(&["libc", "libgcc", "pthread", "vdso"])
+        .build()
+        .expect("failed to start cpu profiler");
+
+    for _ in 0..iterations {
+        black_box(func(black_box(input)));
+    }
+
+    println!("\r{ANSI_ITALIC}Profiled day {day} part {part} ({iterations} runs){ANSI_RESET}");
+
+    #[cfg(feature = "cpu-profile")]
+    write_cpu_profile(&guard, day, part);
+}
+
+#[cfg(feature = "cpu-profile")]
+fn write_cpu_profile(guard: &pprof::ProfilerGuard, day: Day, part: u8) {
+    let report = match guard.report().build() {
+        Ok(report) => report,
+        Err(e) => {
+            eprintln!("Failed to build cpu profile: {e}");
+            return;
+        }
+    };
+
+    let mut stacks: Vec<FoldedStack> = report
+        .data
+        .iter()
+        .map(|(frames, count)| {
+            let stack = std::iter::once(frames.thread_name.clone())
+                .chain(frames.frames.iter().rev().flat_map(|frame| {
+                    frame
+                        .iter()
+                        .rev()
+                        .map(|symbol| symbol.name().replace(';', ":"))
+                }))
+                .collect::<Vec<_>>()
+                .join(";");
+            (stack, u64::try_from(*count).unwrap_or(0))
+        })
+        .collect();
+    stacks.sort_unstable();
+
+    match flamegraph::write_profile(&format!("{day}-{part}"), &stacks) {
+        Ok((folded_path, svg_path)) => {
+            println!("Wrote \"{folded_path}\" and \"{svg_path}\".");
+        }
+        Err(e) => {
+            eprintln!("Failed to write cpu profile: {e}");
+        }
+    }
+}
+
 fn average_duration(numbers: &[Duration]) -> u128 {
     numbers
         .iter()
@@ -128,15 +354,13 @@ fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str)
 /// Parse the arguments passed to `solve` and try to submit one part of the solution if:
 ///  1. we are in `--release` mode.
 ///  2. aoc-cli is installed.
-fn submit_result<T: Display>(
-    result: T,
-    day: Day,
-    part: u8,
-) -> Option<Result<Output, aoc_cli::AocCommandError>> {
+///
+/// When part one is accepted, the puzzle description is downloaded again to show part two.
+fn submit_result<T: Display>(result: T, day: Day, part: u8) {
     let args: Vec<String> = env::args().collect();
 
     if !args.contains(&"--submit".into()) {
-        return None;
+        return;
     }
 
     if args.len() < 3 {
@@ -152,7 +376,7 @@ fn submit_result<T: Display>(
     };
 
     if part_submit != part {
-        return None;
+        return;
     }
 
     if aoc_cli::check().is_err() {
@@ -161,5 +385,68 @@ fn submit_result<T: Display>(
     }
 
     println!("Submitting result via aoc-cli...");
-    Some(aoc_cli::submit(day, part, &result.to_string()))
+    let submission = match aoc_cli::submit(day, part, &result.to_string()) {
+        Ok(submission) => submission,
+        Err(e) => {
+            eprintln!("failed to call aoc-cli: {e}");
+            process::exit(1);
+        }
+    };
+
+    println!();
+    println!("{ANSI_BOLD}{submission}{ANSI_RESET}");
+
+    if submission == Submission::Correct && part == 1 {
+        println!();
+        println!("Downloading part two...");
+        if let Err(e) = aoc_cli::download_puzzle(day) {
+            eprintln!("failed to call aoc-cli: {e}");
+            return;
+        }
+
+        let markdown = read_markdown(day).unwrap_or_default();
+        if let Some(part_two) = markdown::select_part(&markdown, 2) {
+            println!();
+            println!("{}", markdown::render(part_two, 80));
+        }
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use super::{format_variant_table, VariantResult};
+
+    fn variant(name: &str, answer: Option<&str>, millis: u64) -> VariantResult {
+        VariantResult {
+            name: name.into(),
+            answer: answer.map(Into::into),
+            duration: Duration::from_millis(millis),
+            samples: 10,
+        }
+    }
+
+    #[test]
+    fn formats_variant_table() {
+        let table = format_variant_table(&[
+            variant("naive", Some("6"), 100),
+            variant("fast", Some("6"), 10),
+            variant("broken", None, 50),
+        ]);
+
+        assert_eq!(
+            table,
+            [
+                "  naive   6  100.0ms @ 10 runs   1.00x",
+                "  fast    6   10.0ms @ 10 runs  10.00x",
+                "  broken  ✖   50.0ms @ 10 runs   2.00x",
+            ]
+        );
+    }
+
+    #[test]
+    fn formats_empty_variant_table() {
+        assert_eq!(format_variant_table(&[]).is_empty(), true);
+    }
 }
@@ -0,0 +1,72 @@
+use std::fmt::Display;
+
+use crate::template::ParamSet;
+
+/// A typed alternative to the free `part_one` and `part_two` functions.
+///
+/// Register an implementation with `solution!(<day>, <Type>)`. The input is parsed once and shared by both parts.
+/// Part one can hand intermediate results to part two through [`Solution::Carry`]. Both parts receive the
+/// [`Solution::Params`] for the input.
+///
+/// ```
+/// # use advent_of_code::template::Solution;
+/// pub struct Day01;
+///
+/// impl Solution for Day01 {
+///     type Parsed = Vec<u32>;
+///     type Params = ();
+///     type Carry = ();
+///     type Answer1 = u32;
+///     type Answer2 = u32;
+///
+///     fn parse(input: &str) -> Self::Parsed {
+///         input.lines().map(|l| l.parse().unwrap()).collect()
+///     }
+///
+///     fn part_one(parsed: &Self::Parsed, _params: &Self::Params, _carry: &mut Self::Carry) -> Option<Self::Answer1> {
+///         parsed.iter().max().copied()
+///     }
+/// }
+/// ```
+pub trait Solution {
+    /// The parsed puzzle input.
+    type Parsed;
+    /// Parameters declared with [`params!`](crate::params), `()` if there are none.
+    type Params: ParamSet;
+    /// Intermediate results of part one that part two can reuse, `()` if there are none.
+    type Carry: Default;
+    type Answer1: Display;
+    type Answer2: Display;
+
+    fn parse(input: &str) -> Self::Parsed;
+
+    fn part_one(
+        parsed: &Self::Parsed,
+        params: &Self::Params,
+        carry: &mut Self::Carry,
+    ) -> Option<Self::Answer1>;
+
+    /// Solves part two, receiving the intermediate results of part one. Unsolved by default.
+    fn part_two(
+        _parsed: &Self::Parsed,
+        _params: &Self::Params,
+        _carry: &Self::Carry,
+    ) -> Option<Self::Answer2> {
+        None
+    }
+}
+
+/// Helper function that parses the input and solves part one of a [`Solution`].
+pub fn solve_part_one<S: Solution>(input: &str, params: &S::Params) -> Option<S::Answer1> {
+    let parsed = S::parse(input);
+    S::part_one(&parsed, params, &mut S::Carry::default())
+}
+
+/// Helper function that parses the input and solves part two of a [`Solution`].
+/// Part one runs first to provide its intermediate results.
+pub fn solve_part_two<S: Solution>(input: &str, params: &S::Params) -> Option<S::Answer2> {
+    let parsed = S::parse(input);
+    let mut carry = S::Carry::default();
+    S::part_one(&parsed, params, &mut carry);
+    S::part_two(&parsed, params, &carry)
+}
@@ -0,0 +1,75 @@
+/// Module that fingerprints the code and input a day's timings were measured with.
+/// The library is approximated by every source file outside of `src/bin`, so a change to a shared module invalidates every day.
+use std::{fs, path::Path, sync::OnceLock};
+
+use crate::template::run_multi::get_path_for_bin;
+use crate::template::Day;
+
+const FNV_OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0100_0000_01b3;
+
+static LIB_HASH: OnceLock<u64> = OnceLock::new();
+
+/// 64-bit FNV-1a, stable across Rust versions unlike `DefaultHasher`.
+fn fnv1a(hash: u64, bytes: &[u8]) -> u64 {
+    bytes.iter().fold(hash, |hash, b| {
+        (hash ^ u64::from(*b)).wrapping_mul(FNV_PRIME)
+    })
+}
+
+/// Adds a file to the hash, a missing file hashes differently from an empty one.
+fn hash_file(hash: u64, path: &Path) -> u64 {
+    let hash = fnv1a(hash, path.to_string_lossy().as_bytes());
+    match fs::read(path) {
+        Ok(contents) => fnv1a(fnv1a(hash, &[1]), &contents),
+        Err(_) => fnv1a(hash, &[0]),
+    }
+}
+
+/// Collects the `.rs` files below `dir` in a stable order, skipping `src/bin`.
+fn collect_sources(dir: &Path, files: &mut Vec<std::path::PathBuf>) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            if path != Path::new("src/bin") {
+                collect_sources(&path, files);
+            }
+        } else if path.extension().is_some_and(|ext| ext == "rs") {
+            files.push(path);
+        }
+    }
+}
+
+fn lib_hash() -> u64 {
+    *LIB_HASH.get_or_init(|| {
+        let mut files = vec![];
+        collect_sources(Path::new("src"), &mut files);
+        files.sort();
+        files
+            .iter()
+            .fold(FNV_OFFSET, |hash, path| hash_file(hash, path))
+    })
+}
+
+/// Returns a hash of the solution, library sources and input of `day` as a hex string.
+pub fn day_hash(day: Day) -> String {
+    let hash = hash_file(lib_hash(), Path::new(&get_path_for_bin(day)));
+    let hash = hash_file(hash, Path::new(&format!("./data/inputs/{day}.txt")));
+    format!("{hash:016x}")
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{fnv1a, FNV_OFFSET};
+
+    #[test]
+    fn hashes_fnv1a() {
+        assert_eq!(fnv1a(FNV_OFFSET, b""), 0xcbf2_9ce4_8422_2325);
+        assert_eq!(fnv1a(FNV_OFFSET, b"a"), 0xaf63_dc4c_8601_ec8c);
+        assert_eq!(fnv1a(FNV_OFFSET, b"foobar"), 0x8594_4171_f739_67e8);
+    }
+}
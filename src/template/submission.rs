@@ -0,0 +1,102 @@
+/// Module that classifies the response of the Advent of Code server to a submitted answer, as printed by aoc-cli.
+use std::fmt::Display;
+
+/// The outcome of submitting an answer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Submission {
+    Correct,
+    /// The answer was wrong, optionally with a hint like "too high".
+    Incorrect(Option<String>),
+    /// An answer was submitted too recently, optionally with the remaining wait time like "35s".
+    TooSoon(Option<String>),
+    /// The part has already been solved or is not unlocked yet.
+    WrongLevel,
+    Unknown,
+}
+
+impl Submission {
+    /// Classifies the output of `aoc submit`.
+    pub fn classify(output: &str) -> Self {
+        if output.contains("That's the right answer") {
+            Submission::Correct
+        } else if output.contains("That's not the right answer") {
+            let hint = ["too high", "too low"]
+                .into_iter()
+                .find(|hint| output.contains(hint))
+                .map(String::from);
+            Submission::Incorrect(hint)
+        } else if output.contains("You gave an answer too recently") {
+            let wait = output.split_once("You have ").and_then(|(_, rest)| {
+                rest.split_once(" left to wait")
+                    .map(|(wait, _)| wait.trim().to_string())
+            });
+            Submission::TooSoon(wait)
+        } else if output.contains("You don't seem to be solving the right level") {
+            Submission::WrongLevel
+        } else {
+            Submission::Unknown
+        }
+    }
+}
+
+impl Display for Submission {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Submission::Correct => f.write_str("⭐ Correct answer!"),
+            Submission::Incorrect(Some(hint)) => {
+                write!(f, "❌ Wrong answer, your answer is {hint}.")
+            }
+            Submission::Incorrect(None) => f.write_str("❌ Wrong answer."),
+            Submission::TooSoon(Some(wait)) => {
+                write!(f, "⏳ Answer submitted too recently, wait {wait}.")
+            }
+            Submission::TooSoon(None) => f.write_str("⏳ Answer submitted too recently."),
+            Submission::WrongLevel => {
+                f.write_str("This part is either already solved or not unlocked yet.")
+            }
+            Submission::Unknown => {
+                f.write_str("Could not interpret the response to the submission.")
+            }
+        }
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Submission;
+
+    #[test]
+    fn classifies_correct_answers() {
+        let output = "That's the right answer! You are one gold star closer to finding the Chief Historian. [Continue to Part Two]";
+        assert_eq!(Submission::classify(output), Submission::Correct);
+    }
+
+    #[test]
+    fn classifies_wrong_answers() {
+        let output = "That's not the right answer; your answer is too high. If you're stuck, make sure you're using the full input data.";
+        assert_eq!(
+            Submission::classify(output),
+            Submission::Incorrect(Some("too high".into()))
+        );
+        assert_eq!(
+            Submission::classify("That's not the right answer."),
+            Submission::Incorrect(None)
+        );
+    }
+
+    #[test]
+    fn classifies_rate_limits() {
+        let output = "You gave an answer too recently; you have to wait after submitting an answer before trying again. You have 35s left to wait.";
+        assert_eq!(
+            Submission::classify(output),
+            Submission::TooSoon(Some("35s".into()))
+        );
+    }
+
+    #[test]
+    fn classifies_wrong_levels() {
+        let output = "You don't seem to be solving the right level. Did you already complete it?";
+        assert_eq!(Submission::classify(output), Submission::WrongLevel);
+        assert_eq!(Submission::classify(""), Submission::Unknown);
+    }
+}
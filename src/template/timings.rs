@@ -9,9 +9,12 @@ static TIMINGS_FILE_PATH: &str = "./data/timings.json";
 #[derive(Clone, Debug)]
 pub struct Timing {
     pub day: Day,
+    pub parse: Option<String>,
     pub part_1: Option<String>,
     pub part_2: Option<String>,
     pub total_nanos: f64,
+    /// Hash of the code and input the timing was measured with, see [`day_hash`](super::source_hash::day_hash).
+    pub hash: Option<String>,
 }
 
 /// Represents benchmark times for a set of days.
@@ -51,7 +54,7 @@ impl Timings {
             }
         }
 
-        data.sort_unstable_by(|a, b| a.day.cmp(&b.day));
+        data.sort_unstable_by_key(|t| t.day);
         Timings { data }
     }
 
@@ -65,6 +68,16 @@ impl Timings {
             .iter()
             .any(|t| t.day == day && t.part_1.is_some() && t.part_2.is_some())
     }
+
+    /// Whether a day is complete and was measured with the code and input hashing to `hash`.
+    /// Timings stored without a hash are treated as outdated.
+    pub fn is_day_up_to_date(&self, day: Day, hash: &str) -> bool {
+        self.is_day_complete(day)
+            && self
+                .data
+                .iter()
+                .any(|t| t.day == day && t.hash.as_deref() == Some(hash))
+    }
 }
 
 /* -------------------------------------------------------------------------- */
@@ -114,9 +127,22 @@ impl From<&Timing> for JsonValue {
         map.insert("day".into(), JsonValue::String(value.day.to_string()));
         map.insert("total_nanos".into(), JsonValue::Number(value.total_nanos));
 
+        let parse = value.parse.clone().map(JsonValue::String);
         let part_1 = value.part_1.clone().map(JsonValue::String);
         let part_2 = value.part_2.clone().map(JsonValue::String);
 
+        if let Some(hash) = &value.hash {
+            map.insert("hash".into(), JsonValue::String(hash.clone()));
+        }
+
+        map.insert(
+            "parse".into(),
+            match parse {
+                Some(x) => x,
+                None => JsonValue::Null,
+            },
+        );
+
         map.insert(
             "part_1".into(),
             match part_1 {
@@ -151,6 +177,16 @@ impl TryFrom<&JsonValue> for Timing {
             .and_then(|day| Day::from_str(day).ok())
             .ok_or("Expected timing.day to be a Day struct.")?;
 
+        // NOTE: `parse` was added later, treat timings stored before as not having a parse phase.
+        let parse = match json.get("parse") {
+            None => None,
+            Some(v) if v.is_null() => None,
+            Some(v) => Some(
+                v.get::<String>()
+                    .ok_or("Expected timing.parse to be null or string.")?,
+            ),
+        };
+
         let part_1 = json
             .get("part_1")
             .map(|v| if v.is_null() { None } else { v.get::<String>() })
@@ -166,11 +202,16 @@ impl TryFrom<&JsonValue> for Timing {
             .and_then(|v| v.get::<f64>().copied())
             .ok_or("Expected timing.total_nanos to be a number.")?;
 
+        // NOTE: `hash` was added later, timings stored before are re-benched once.
+        let hash = json.get("hash").and_then(|v| v.get::<String>()).cloned();
+
         Ok(Timing {
             day,
+            parse: parse.cloned(),
             part_1: part_1.cloned(),
             part_2: part_2.cloned(),
             total_nanos,
+            hash,
         })
     }
 }
@@ -188,21 +229,27 @@ mod tests {
             data: vec![
                 Timing {
                     day: day!(1),
+                    parse: None,
                     part_1: Some("10ms".into()),
                     part_2: Some("20ms".into()),
                     total_nanos: 3e+10,
+                    hash: None,
                 },
                 Timing {
                     day: day!(2),
+                    parse: None,
                     part_1: Some("30ms".into()),
                     part_2: Some("40ms".into()),
                     total_nanos: 7e+10,
+                    hash: None,
                 },
                 Timing {
                     day: day!(4),
+                    parse: None,
                     part_1: Some("40ms".into()),
                     part_2: None,
                     total_nanos: 4e+10,
+                    hash: None,
                 },
             ],
         }
@@ -223,6 +270,26 @@ mod tests {
             assert_eq!(timing.total_nanos, 1_000_000_000_f64);
         }
 
+        #[test]
+        fn handles_parse_timings() {
+            let json = r#"{ "data": [{ "day": "01", "parse": "2ms", "part_1": "1ms", "part_2": null, "total_nanos": 3000000 }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            let timing = timings.data.first().unwrap();
+            assert_eq!(timing.parse, Some("2ms".to_string()));
+            assert_eq!(timing.part_1, Some("1ms".to_string()));
+        }
+
+        #[test]
+        fn handles_hashes() {
+            let json = r#"{ "data": [{ "day": "01", "part_1": "1ms", "part_2": null, "total_nanos": 1000000, "hash": "00ff" }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            assert_eq!(timings.data[0].hash, Some("00ff".to_string()));
+
+            let json = r#"{ "data": [{ "day": "01", "part_1": "1ms", "part_2": null, "total_nanos": 1000000 }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            assert_eq!(timings.data[0].hash, None);
+        }
+
         #[test]
         fn handles_empty_timings() {
             let json = r#"{ "data": [] }"#.to_string();
@@ -274,18 +341,22 @@ mod tests {
             template::timings::{Timing, Timings},
         };
 
+        use super::get_mock_timings;
+
         #[test]
         fn handles_completed_days() {
             let timings = Timings {
                 data: vec![Timing {
                     day: day!(1),
+                    parse: None,
                     part_1: Some("1ms".into()),
                     part_2: Some("2ms".into()),
                     total_nanos: 3_000_000_000_f64,
+                    hash: None,
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), true);
+            assert_eq!(timings.is_day_complete(day!(1)), true);
         }
 
         #[test]
@@ -293,13 +364,15 @@ mod tests {
             let timings = Timings {
                 data: vec![Timing {
                     day: day!(1),
+                    parse: None,
                     part_1: Some("1ms".into()),
                     part_2: None,
                     total_nanos: 1_000_000_000_f64,
+                    hash: None,
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(day!(1)), false);
         }
 
         #[test]
@@ -307,13 +380,38 @@ mod tests {
             let timings = Timings {
                 data: vec![Timing {
                     day: day!(1),
+                    parse: None,
                     part_1: None,
                     part_2: None,
                     total_nanos: 0.0,
+                    hash: None,
+                }],
+            };
+
+            assert_eq!(timings.is_day_complete(day!(1)), false);
+        }
+
+        #[test]
+        fn handles_changed_hashes() {
+            let timings = Timings {
+                data: vec![Timing {
+                    day: day!(1),
+                    parse: None,
+                    part_1: Some("1ms".into()),
+                    part_2: Some("2ms".into()),
+                    total_nanos: 3_000_000_000_f64,
+                    hash: Some("abc".into()),
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_up_to_date(day!(1), "abc"), true);
+            assert_eq!(timings.is_day_up_to_date(day!(1), "def"), false);
+        }
+
+        #[test]
+        fn handles_missing_hashes() {
+            let timings = get_mock_timings();
+            assert_eq!(timings.is_day_up_to_date(day!(1), "abc"), false);
         }
     }
 
@@ -331,9 +429,11 @@ mod tests {
             let other = Timings {
                 data: vec![Timing {
                     day: day!(3),
+                    parse: None,
                     part_1: None,
                     part_2: None,
                     total_nanos: 0_f64,
+                    hash: None,
                 }],
             };
             let merged = timings.merge(&other);
@@ -351,9 +451,11 @@ mod tests {
             let other = Timings {
                 data: vec![Timing {
                     day: day!(2),
+                    parse: None,
                     part_1: None,
                     part_2: None,
                     total_nanos: 0_f64,
+                    hash: None,
                 }],
             };
             let merged = timings.merge(&other);